@@ -6,7 +6,9 @@ use rae_app::window;
 
 use rae_math::geometry3;
 
-use rae_gfx::core::{ColorF32, InstanceCreationError, SwapChainError};
+use rae_gfx::core::{
+    CanvasBufferCreationError, CanvasFrameError, ColorF32, InstanceCreationError, RenderPassError,
+};
 
 pub type ApplicationEvent = ();
 
@@ -14,7 +16,9 @@ pub type ApplicationEvent = ();
 pub enum ApplicationError {
     WindowCreationFailed(window::OsError),
     InstanceCreationFailed(InstanceCreationError),
-    RenderFrameCreationFailed(SwapChainError),
+    RenderFrameCreationFailed(CanvasFrameError),
+    CanvasBufferCreationFailed(CanvasBufferCreationError),
+    RenderPassCreationFailed(RenderPassError),
 }
 
 impl std::fmt::Display for ApplicationError {
@@ -29,6 +33,12 @@ impl std::fmt::Display for ApplicationError {
             ApplicationError::RenderFrameCreationFailed(e) => {
                 write!(f, "Render frame creation failed ({})", e)
             }
+            ApplicationError::CanvasBufferCreationFailed(e) => {
+                write!(f, "Canvas buffer creation failed ({})", e)
+            }
+            ApplicationError::RenderPassCreationFailed(e) => {
+                write!(f, "Render pass creation failed ({})", e)
+            }
         }
     }
 }
@@ -39,6 +49,8 @@ impl std::error::Error for ApplicationError {
             ApplicationError::WindowCreationFailed(e) => Some(e),
             ApplicationError::InstanceCreationFailed(e) => Some(e),
             ApplicationError::RenderFrameCreationFailed(e) => Some(e),
+            ApplicationError::CanvasBufferCreationFailed(e) => Some(e),
+            ApplicationError::RenderPassCreationFailed(e) => Some(e),
         }
     }
 }
@@ -55,12 +67,24 @@ impl From<InstanceCreationError> for ApplicationError {
     }
 }
 
-impl From<SwapChainError> for ApplicationError {
-    fn from(e: SwapChainError) -> Self {
+impl From<CanvasFrameError> for ApplicationError {
+    fn from(e: CanvasFrameError) -> Self {
         ApplicationError::RenderFrameCreationFailed(e)
     }
 }
 
+impl From<CanvasBufferCreationError> for ApplicationError {
+    fn from(e: CanvasBufferCreationError) -> Self {
+        ApplicationError::CanvasBufferCreationFailed(e)
+    }
+}
+
+impl From<RenderPassError> for ApplicationError {
+    fn from(e: RenderPassError) -> Self {
+        ApplicationError::RenderPassCreationFailed(e)
+    }
+}
+
 #[derive(Debug)]
 pub struct ChangingColor {
     current_color: ColorF32,
@@ -12,9 +12,9 @@ use rae_math::{
 
 use rae_gfx::{
     core::{
-        AddressMode, Canvas, CanvasWindow, CanvasWindowDescriptor, ColorF32, CommandSequence,
-        FilterMode, Instance, InstanceDescriptor, RenderPassOperations, SampleCount, Sampler,
-        SamplerDescriptor, Texture, TextureUsage, TextureViewDescriptor,
+        AddressMode, Canvas, CanvasWindow, CanvasWindowDescriptor, ColorF32, ColorSpace,
+        CommandSequence, FilterMode, Instance, InstanceDescriptor, RenderPassOperations,
+        SampleCount, Sampler, SamplerDescriptor, Texture, TextureUsage, TextureViewDescriptor,
     },
     sprite,
     sprite::{MeshTemplates as SpriteMeshTemplates, Renderer as SpriteRenderer},
@@ -43,11 +43,17 @@ impl ApplicationImpl {
     const SAMPLE_COUNT: SampleCount = 8;
 
     fn create_sprites(instance: &Instance) -> Vec<Sprite> {
-        let image = image::open("examples/data/gioconda.jpg")
-            .expect("Failed to load texture image")
-            .into_rgba();
-        let sprite_texture = Texture::from_image(instance, &image, TextureUsage::SAMPLED)
-            .create_view(&TextureViewDescriptor::default());
+        let image =
+            image::open("examples/data/gioconda.jpg").expect("Failed to load texture image");
+        let sprite_texture = Texture::from_image_with_mipmaps(
+            instance,
+            Some("gioconda"),
+            &image,
+            ColorSpace::Srgb,
+            TextureUsage::SAMPLED,
+            FilterMode::Linear,
+        )
+        .create_view(&TextureViewDescriptor::default());
 
         vec![
             Sprite {
@@ -208,7 +214,7 @@ impl EventHandler<ApplicationError, ApplicationEvent> for ApplicationImpl {
                     sample_count: Self::SAMPLE_COUNT,
                     ..CanvasWindowDescriptor::default()
                 },
-            );
+            )?;
             (window, instance)
         };
 
@@ -250,7 +256,7 @@ impl EventHandler<ApplicationError, ApplicationEvent> for ApplicationImpl {
         size: window::PhysicalSize<u32>,
     ) -> Result<ControlFlow, Self::Error> {
         if wid == self.window.id() {
-            self.window.update_buffer(&self.instance);
+            self.window.update_buffer(&self.instance)?;
             self.projection_transform = OrthographicProjection::new(
                 0.,
                 1f32.max(size.width as f32),
@@ -269,14 +275,14 @@ impl EventHandler<ApplicationError, ApplicationEvent> for ApplicationImpl {
             *self.color.current_color(),
         );
 
-        let frame = self.window.current_frame()?;
+        let frame = self.window.current_frame(&self.instance)?;
         let mut cmd_sequence = CommandSequence::new(&self.instance);
         {
             let mut rpass = cmd_sequence.begin_render_pass(
                 &frame,
                 &self.pipeline.render_pass_requirements(),
                 &RenderPassOperations::default(),
-            );
+            )?;
 
             // Single draw
             // for sprite in &self.sprites {
@@ -36,6 +36,7 @@ struct ApplicationImpl {
     triangle_mesh: shape2::Mesh,
     sprite_pipeline: sprite::RenderPipeline,
     quad_mesh: sprite::Mesh,
+    sampler: Sampler,
     sprite_uniform_constants: sprite::UniformConstants,
     current_angle: f32,
     color: ChangingColor,
@@ -43,6 +44,7 @@ struct ApplicationImpl {
 
 impl ApplicationImpl {
     const SAMPLE_COUNT: SampleCount = 8;
+    const CANVAS_SIZE_DIVISOR: u32 = 8;
     const SCREENSHOT_PATH: &'static str = "screenshot.png";
 
     pub fn update_angle(&mut self, dt: std::time::Duration) {
@@ -66,6 +68,23 @@ impl ApplicationImpl {
         )
     }
 
+    pub fn canvas_size_for_window(window: &CanvasWindow) -> Size<u32> {
+        let window_size = window.inner_size();
+        Size::new(
+            std::cmp::max(1, window_size.width / Self::CANVAS_SIZE_DIVISOR),
+            std::cmp::max(1, window_size.height / Self::CANVAS_SIZE_DIVISOR),
+        )
+    }
+
+    pub fn create_sprite_uniform_constants(
+        instance: &Instance,
+        canvas: &CanvasTexture,
+        sampler: &Sampler,
+    ) -> sprite::UniformConstants {
+        sprite::UniformConstants::from_canvas_texture(instance, canvas, 0, sampler)
+            .expect("The canvas color buffer doesn't exist")
+    }
+
     pub fn generate_blit_push_constants(&self) -> sprite::PushConstants {
         let projection_transform = OrthographicProjection::new(0., 1., 1., 0.).to_projective();
         sprite::PushConstants::new(&convert(projection_transform), ColorF32::WHITE)
@@ -93,22 +112,22 @@ impl EventHandler<ApplicationError, ApplicationEvent> for ApplicationImpl {
                 window,
                 surface,
                 &CanvasWindowDescriptor::default(),
-            );
+            )?;
             (window, instance)
         };
 
         let canvas = CanvasTexture::new(
             &instance,
             &CanvasTextureDescriptor {
-                size: Size::new(100, 100),
+                size: Self::canvas_size_for_window(&window),
                 sample_count: Self::SAMPLE_COUNT,
-                color_buffer_descriptor: Some(CanvasTextureColorBufferDescriptor {
+                color_buffer_descriptors: vec![CanvasTextureColorBufferDescriptor {
                     format: CanvasColorBufferFormat::Rgba8UnormSrgb,
                     usage: CanvasColorBufferUsage::SAMPLED | CanvasColorBufferUsage::COPY_SRC,
-                }),
+                }],
                 ..CanvasTextureDescriptor::default()
             },
-        );
+        )?;
 
         let shape2_pipeline = shape2::RenderPipeline::new(
             &instance,
@@ -147,11 +166,8 @@ impl EventHandler<ApplicationError, ApplicationEvent> for ApplicationImpl {
             },
         );
 
-        let canvas_texture_view = canvas
-            .color_texture_view()
-            .expect("The canvas color buffer doesn't exist");
         let sprite_uniform_constants =
-            sprite::UniformConstants::new(&instance, canvas_texture_view, &sampler);
+            Self::create_sprite_uniform_constants(&instance, &canvas, &sampler);
 
         let color = ChangingColor::new(ColorF32::WHITE, ColorF32::WHITE);
 
@@ -163,6 +179,7 @@ impl EventHandler<ApplicationError, ApplicationEvent> for ApplicationImpl {
             triangle_mesh,
             sprite_pipeline,
             quad_mesh,
+            sampler,
             sprite_uniform_constants,
             current_angle: 0.,
             color,
@@ -182,7 +199,7 @@ impl EventHandler<ApplicationError, ApplicationEvent> for ApplicationImpl {
                 if key == keyboard::KeyCode::Return {
                     let image = self
                         .canvas
-                        .color_texture()
+                        .color_texture(0)
                         .unwrap()
                         .to_image(&self.instance);
                     image.save(Self::SCREENSHOT_PATH).unwrap();
@@ -205,7 +222,16 @@ impl EventHandler<ApplicationError, ApplicationEvent> for ApplicationImpl {
         _size: window::PhysicalSize<u32>,
     ) -> Result<ControlFlow, Self::Error> {
         if wid == self.window.id() {
-            self.window.update_buffer(&self.instance);
+            self.window.update_buffer(&self.instance)?;
+            self.canvas
+                .resize(&self.instance, Self::canvas_size_for_window(&self.window));
+            if self.sprite_uniform_constants.is_outdated(&self.canvas) {
+                self.sprite_uniform_constants = Self::create_sprite_uniform_constants(
+                    &self.instance,
+                    &self.canvas,
+                    &self.sampler,
+                );
+            }
         }
         Ok(ControlFlow::Continue)
     }
@@ -217,7 +243,7 @@ impl EventHandler<ApplicationError, ApplicationEvent> for ApplicationImpl {
         {
             // Render a triangle onto the canvas texture.
             let push_constants = self.generate_triangle_push_constants();
-            let frame = self.canvas.current_frame()?;
+            let frame = self.canvas.current_frame(&self.instance)?;
             let mut cmd_sequence = CommandSequence::new(&self.instance);
             {
                 let mut rpass = cmd_sequence.begin_render_pass(
@@ -230,7 +256,7 @@ impl EventHandler<ApplicationError, ApplicationEvent> for ApplicationImpl {
                         }],
                         ..RenderPassOperations::default()
                     },
-                );
+                )?;
                 rpass.draw_shape2(
                     &self.shape2_pipeline,
                     &self.triangle_mesh,
@@ -243,7 +269,7 @@ impl EventHandler<ApplicationError, ApplicationEvent> for ApplicationImpl {
         {
             // Render the canvas texture onto the canvas window.
             let push_constants = self.generate_blit_push_constants();
-            let frame = self.window.current_frame()?;
+            let frame = self.window.current_frame(&self.instance)?;
             let mut cmd_sequence = CommandSequence::new(&self.instance);
             {
                 let mut rpass = cmd_sequence.begin_render_pass(
@@ -256,7 +282,7 @@ impl EventHandler<ApplicationError, ApplicationEvent> for ApplicationImpl {
                         }],
                         ..RenderPassOperations::default()
                     },
-                );
+                )?;
                 rpass.draw_sprite(
                     &self.sprite_pipeline,
                     &self.sprite_uniform_constants,
@@ -0,0 +1,24 @@
+use rae_gfx::core::replay_trace;
+
+// Replays a trace recorded through InstanceDescriptor::trace_path and saves
+// the final canvas as an image.
+// Usage: cargo run --example replay_trace --features replay -- <trace directory> <output.png>
+fn main() {
+    let args: Vec<String> = std::env::args().collect();
+    if args.len() != 3 {
+        eprintln!("Usage: replay_trace <trace directory> <output.png>");
+        std::process::exit(1);
+    }
+
+    let image = match replay_trace(&args[1]) {
+        Ok(image) => image,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+    if let Err(e) = image.save(&args[2]) {
+        eprintln!("Failed to save the canvas image ({})", e);
+        std::process::exit(1);
+    }
+}
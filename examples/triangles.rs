@@ -88,7 +88,7 @@ impl EventHandler<ApplicationError, ApplicationEvent> for ApplicationImpl {
                     sample_count: Self::SAMPLE_COUNT,
                     ..CanvasWindowDescriptor::default()
                 },
-            );
+            )?;
             (window, instance)
         };
 
@@ -152,7 +152,7 @@ impl EventHandler<ApplicationError, ApplicationEvent> for ApplicationImpl {
         size: window::PhysicalSize<u32>,
     ) -> Result<ControlFlow, Self::Error> {
         if wid == self.window.id() {
-            self.window.update_buffer(&self.instance);
+            self.window.update_buffer(&self.instance)?;
             self.projection_transform = OrthographicProjection::new(
                 0.,
                 1f32.max(size.width as f32),
@@ -211,7 +211,7 @@ impl EventHandler<ApplicationError, ApplicationEvent> for ApplicationImpl {
 
         let current_triangle_constants = self.generate_push_constant();
 
-        let frame = self.window.current_frame()?;
+        let frame = self.window.current_frame(&self.instance)?;
         let mut cmd_sequence = CommandSequence::new(&self.instance);
 
         {
@@ -219,7 +219,7 @@ impl EventHandler<ApplicationError, ApplicationEvent> for ApplicationImpl {
                 &frame,
                 &self.pipeline.render_pass_requirements(),
                 &RenderPassOperations::default(),
-            );
+            )?;
             rpass.draw_shape2_array(
                 &self.pipeline,
                 once((&self.triangle_mesh, draw_static_triangle_params)),
@@ -239,7 +239,7 @@ impl EventHandler<ApplicationError, ApplicationEvent> for ApplicationImpl {
                     }],
                     ..RenderPassOperations::default()
                 },
-            );
+            )?;
             rpass.draw_shape2(
                 &self.pipeline,
                 &self.triangle_mesh,
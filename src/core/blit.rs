@@ -0,0 +1,147 @@
+use super::{
+    AddressMode, BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+    BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType, BlendDescriptor,
+    ColorStateDescriptor, ColorWrite, CullMode, FilterMode, FrontFace, IndexFormat, Instance,
+    PipelineLayout, PipelineLayoutDescriptor, PrimitiveTopology, ProgrammableStageDescriptor,
+    RasterizationStateDescriptor, RenderPass, RenderPipeline, RenderPipelineDescriptor,
+    SampleCount, Sampler, SamplerDescriptor, ShaderModule, ShaderStage, TextureComponentType,
+    TextureFormat, TextureView, TextureViewDimension, VertexStateDescriptor,
+};
+
+// Draws a sampled texture over the whole render target with a fullscreen
+// triangle.
+#[derive(Debug)]
+pub(crate) struct BlitPipeline {
+    bind_group_layout: BindGroupLayout,
+    pipeline: RenderPipeline,
+    sampler: Sampler,
+}
+
+impl BlitPipeline {
+    pub fn new(
+        instance: &Instance,
+        format: TextureFormat,
+        sample_count: SampleCount,
+        filter: FilterMode,
+    ) -> Self {
+        let bind_group_layout = BindGroupLayout::new(
+            instance,
+            &BindGroupLayoutDescriptor {
+                label: Some("blit bind group layout"),
+                entries: &[
+                    BindGroupLayoutEntry {
+                        binding: 0,
+                        visibility: ShaderStage::FRAGMENT,
+                        ty: BindingType::SampledTexture {
+                            multisampled: false,
+                            component_type: TextureComponentType::Float,
+                            dimension: TextureViewDimension::D2,
+                        },
+                        count: None,
+                    },
+                    BindGroupLayoutEntry {
+                        binding: 1,
+                        visibility: ShaderStage::FRAGMENT,
+                        ty: BindingType::Sampler { comparison: false },
+                        count: None,
+                    },
+                ],
+            },
+        );
+        let pipeline_layout = PipelineLayout::new(
+            instance,
+            &PipelineLayoutDescriptor {
+                label: Some("blit pipeline layout"),
+                bind_group_layouts: &[&bind_group_layout],
+                push_constant_ranges: &[],
+            },
+        );
+        let vs_module = ShaderModule::new(
+            instance,
+            super::include_spirv!("shaders/gen/spirv/blit.vert.spv"),
+        );
+        let fs_module = ShaderModule::new(
+            instance,
+            super::include_spirv!("shaders/gen/spirv/blit.frag.spv"),
+        );
+        let pipeline = RenderPipeline::new(
+            instance,
+            &RenderPipelineDescriptor {
+                label: Some("blit render pipeline"),
+                layout: Some(&pipeline_layout),
+                vertex_stage: ProgrammableStageDescriptor {
+                    module: &vs_module,
+                    entry_point: "main",
+                },
+                fragment_stage: Some(ProgrammableStageDescriptor {
+                    module: &fs_module,
+                    entry_point: "main",
+                }),
+                rasterization_state: Some(RasterizationStateDescriptor {
+                    front_face: FrontFace::Ccw,
+                    cull_mode: CullMode::None,
+                    ..Default::default()
+                }),
+                primitive_topology: PrimitiveTopology::TriangleList,
+                color_states: &[ColorStateDescriptor {
+                    format,
+                    color_blend: BlendDescriptor::REPLACE,
+                    alpha_blend: BlendDescriptor::REPLACE,
+                    write_mask: ColorWrite::ALL,
+                }],
+                depth_stencil_state: None,
+                vertex_state: VertexStateDescriptor {
+                    index_format: IndexFormat::Uint16,
+                    vertex_buffers: &[],
+                },
+                sample_count,
+                sample_mask: !0,
+                alpha_to_coverage_enabled: false,
+            },
+        );
+        let sampler = Sampler::new(
+            instance,
+            &SamplerDescriptor {
+                label: Some("blit sampler"),
+                address_mode_u: AddressMode::ClampToEdge,
+                address_mode_v: AddressMode::ClampToEdge,
+                address_mode_w: AddressMode::ClampToEdge,
+                mag_filter: filter,
+                min_filter: filter,
+                mipmap_filter: FilterMode::Nearest,
+                ..SamplerDescriptor::default()
+            },
+        );
+        Self {
+            bind_group_layout,
+            pipeline,
+            sampler,
+        }
+    }
+
+    pub fn bind_group(&self, instance: &Instance, source: &TextureView) -> BindGroup {
+        BindGroup::new(
+            instance,
+            &BindGroupDescriptor {
+                label: Some("blit bind group"),
+                layout: &self.bind_group_layout,
+                entries: &[
+                    BindGroupEntry {
+                        binding: 0,
+                        resource: BindingResource::TextureView(source),
+                    },
+                    BindGroupEntry {
+                        binding: 1,
+                        resource: BindingResource::Sampler(&self.sampler),
+                    },
+                ],
+            },
+        )
+    }
+
+    pub fn draw<'a>(&'a self, pass: &mut RenderPass<'a>, bind_group: &'a BindGroup) {
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, bind_group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+}
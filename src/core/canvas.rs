@@ -10,6 +10,24 @@ pub enum CanvasColorBufferFormat {
     Rgba8UnormSrgb,
     Bgra8Unorm,
     Bgra8UnormSrgb,
+    Rgba16Float,
+    Rgba32Float,
+    Rgb10a2Unorm,
+    R8Unorm,
+    R32Float,
+}
+
+impl CanvasColorBufferFormat {
+    // High dynamic range and single channel formats can only be used for
+    // offscreen rendering.
+    pub fn is_presentable(self) -> bool {
+        match self {
+            Self::Rgba8Unorm | Self::Rgba8UnormSrgb | Self::Bgra8Unorm | Self::Bgra8UnormSrgb => {
+                true
+            }
+            _ => false,
+        }
+    }
 }
 
 impl Default for CanvasColorBufferFormat {
@@ -31,6 +49,11 @@ impl From<CanvasColorBufferFormat> for TextureFormat {
             CanvasColorBufferFormat::Rgba8UnormSrgb => TextureFormat::Rgba8UnormSrgb,
             CanvasColorBufferFormat::Bgra8Unorm => TextureFormat::Bgra8Unorm,
             CanvasColorBufferFormat::Bgra8UnormSrgb => TextureFormat::Bgra8UnormSrgb,
+            CanvasColorBufferFormat::Rgba16Float => TextureFormat::Rgba16Float,
+            CanvasColorBufferFormat::Rgba32Float => TextureFormat::Rgba32Float,
+            CanvasColorBufferFormat::Rgb10a2Unorm => TextureFormat::Rgb10a2Unorm,
+            CanvasColorBufferFormat::R8Unorm => TextureFormat::R8Unorm,
+            CanvasColorBufferFormat::R32Float => TextureFormat::R32Float,
         }
     }
 }
@@ -88,11 +111,13 @@ impl<'a> CanvasSwapChainRef<'a> {
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct CanvasSwapChainDescriptor {
+    pub label: Option<String>,
     pub size: CanvasSize,
     pub sample_count: SampleCount,
     pub format: CanvasColorBufferFormat,
+    pub present_mode: PresentMode,
 }
 
 #[derive(Debug)]
@@ -100,12 +125,22 @@ pub struct CanvasSwapChain {
     size: CanvasSize,
     sample_count: SampleCount,
     format: CanvasColorBufferFormat,
+    present_mode: PresentMode,
     multisampled_buffer: Option<TextureView>,
     swap_chain: SwapChain,
 }
 
 impl CanvasSwapChain {
-    pub fn new(instance: &Instance, surface: &Surface, desc: &CanvasSwapChainDescriptor) -> Self {
+    pub fn new(
+        instance: &Instance,
+        surface: &Surface,
+        desc: &CanvasSwapChainDescriptor,
+    ) -> Result<Self, CanvasBufferCreationError> {
+        if !desc.format.is_presentable() {
+            return Err(CanvasBufferCreationError::UnpresentableSwapChainFormat(
+                desc.format,
+            ));
+        }
         let usage = TextureUsage::OUTPUT_ATTACHMENT;
         let texture_format = TextureFormat::from(desc.format);
         let width = desc.size.width();
@@ -118,7 +153,7 @@ impl CanvasSwapChain {
                 format: texture_format,
                 width,
                 height,
-                present_mode: PresentMode::Mailbox,
+                present_mode: desc.present_mode,
             },
         );
         let multisampled_buffer = if desc.sample_count > 1 {
@@ -135,20 +170,21 @@ impl CanvasSwapChain {
                     dimension: TextureDimension::D2,
                     format: texture_format,
                     usage,
-                    label: None,
+                    label: desc.label.as_deref(),
                 },
             );
             Some(multisampling_buffer_texture.create_view(&TextureViewDescriptor::default()))
         } else {
             None
         };
-        Self {
+        Ok(Self {
             size: desc.size,
             sample_count: desc.sample_count,
             format: desc.format,
+            present_mode: desc.present_mode,
             multisampled_buffer,
             swap_chain,
-        }
+        })
     }
 
     pub fn size(&self) -> &CanvasSize {
@@ -163,18 +199,30 @@ impl CanvasSwapChain {
         self.format
     }
 
+    pub fn requested_present_mode(&self) -> PresentMode {
+        self.present_mode
+    }
+
     pub fn reference(&mut self) -> Result<CanvasSwapChainRef, SwapChainError> {
-        let frame = self.swap_chain.get_current_frame()?;
+        let frame = self.next_frame()?;
+        Ok(self.reference_from_frame(frame))
+    }
+
+    fn next_frame(&mut self) -> Result<SwapChainFrame, SwapChainError> {
+        self.swap_chain.get_current_frame()
+    }
+
+    fn reference_from_frame(&self, frame: SwapChainFrame) -> CanvasSwapChainRef {
         let multisampled_buffer = match self.multisampled_buffer {
             Some(ref v) => Some(v),
             None => None,
         };
-        Ok(CanvasSwapChainRef {
+        CanvasSwapChainRef {
             sample_count: self.sample_count,
             format: self.format,
             multisampled_buffer,
             frame,
-        })
+        }
     }
 }
 
@@ -225,8 +273,9 @@ impl From<CanvasColorBufferUsage> for TextureUsage {
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+#[derive(Debug, PartialEq, Clone)]
 pub struct CanvasColorBufferDescriptor {
+    pub label: Option<String>,
     pub size: CanvasSize,
     pub sample_count: SampleCount,
     pub format: CanvasColorBufferFormat,
@@ -256,7 +305,7 @@ impl CanvasColorBuffer {
             dimension: TextureDimension::D2,
             format: TextureFormat::from(desc.format),
             usage: TextureUsage::from(desc.usage) | TextureUsage::OUTPUT_ATTACHMENT,
-            label: None,
+            label: desc.label.as_deref(),
         };
 
         let main_buffer_texture = Texture::new(instance, &tex_desc);
@@ -334,11 +383,28 @@ impl<'a> CanvasDepthStencilBufferRef<'a> {
     }
 }
 
-#[derive(Debug, PartialEq, Clone, Copy)]
+bitflags::bitflags! {
+    #[derive(serde::Serialize, serde::Deserialize)]
+    pub struct CanvasDepthStencilBufferUsage : u32 {
+        const COPY_SRC = TextureUsage::COPY_SRC.bits();
+        const COPY_DST = TextureUsage::COPY_DST.bits();
+        const SAMPLED = TextureUsage::SAMPLED.bits();
+    }
+}
+
+impl From<CanvasDepthStencilBufferUsage> for TextureUsage {
+    fn from(usage: CanvasDepthStencilBufferUsage) -> Self {
+        TextureUsage::from_bits(usage.bits()).unwrap()
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
 pub struct CanvasDepthStencilBufferDescriptor {
+    pub label: Option<String>,
     pub size: CanvasSize,
     pub sample_count: SampleCount,
     pub format: CanvasDepthStencilBufferFormat,
+    pub usage: CanvasDepthStencilBufferUsage,
 }
 
 #[derive(Debug)]
@@ -364,8 +430,8 @@ impl CanvasDepthStencilBuffer {
                 sample_count: desc.sample_count,
                 dimension: TextureDimension::D2,
                 format: TextureFormat::from(desc.format),
-                usage: TextureUsage::OUTPUT_ATTACHMENT,
-                label: None,
+                usage: TextureUsage::from(desc.usage) | TextureUsage::OUTPUT_ATTACHMENT,
+                label: desc.label.as_deref(),
             },
         );
         let buffer_view = buffer_texture.create_view(&TextureViewDescriptor::default());
@@ -432,6 +498,7 @@ impl<'a> CanvasFrame<'a> {
 pub struct CanvasBufferSwapChainDescriptor<'a> {
     pub surface: &'a Surface,
     pub format: CanvasColorBufferFormat,
+    pub present_mode: PresentMode,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy, serde::Serialize, serde::Deserialize)]
@@ -449,13 +516,29 @@ impl Default for CanvasBufferColorBufferDescriptor {
     }
 }
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub struct CanvasBufferDepthStencilBufferDescriptor {
+    pub format: CanvasDepthStencilBufferFormat,
+    pub usage: CanvasDepthStencilBufferUsage,
+}
+
+impl Default for CanvasBufferDepthStencilBufferDescriptor {
+    fn default() -> Self {
+        Self {
+            format: CanvasDepthStencilBufferFormat::Depth32Float,
+            usage: CanvasDepthStencilBufferUsage::empty(),
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct CanvasBufferDescriptor<'a> {
+    pub label: Option<String>,
     pub size: CanvasSize,
     pub sample_count: SampleCount,
     pub swap_chain_descriptor: Option<CanvasBufferSwapChainDescriptor<'a>>,
     pub color_buffer_descriptors: Vec<CanvasBufferColorBufferDescriptor>,
-    pub depth_stencil_buffer_format: Option<CanvasDepthStencilBufferFormat>,
+    pub depth_stencil_buffer_descriptor: Option<CanvasBufferDepthStencilBufferDescriptor>,
 }
 
 #[derive(Debug)]
@@ -468,25 +551,38 @@ pub struct CanvasBuffer {
 }
 
 impl CanvasBuffer {
-    pub fn new(instance: &Instance, desc: &CanvasBufferDescriptor) -> Self {
+    pub fn new(
+        instance: &Instance,
+        desc: &CanvasBufferDescriptor,
+    ) -> Result<Self, CanvasBufferCreationError> {
+        if desc.swap_chain_descriptor.is_none()
+            && desc.color_buffer_descriptors.is_empty()
+            && desc.depth_stencil_buffer_descriptor.is_none()
+        {
+            return Err(CanvasBufferCreationError::NoBuffer);
+        }
+
         let swap_chain = match &desc.swap_chain_descriptor {
             Some(sc_desc) => Some(CanvasSwapChain::new(
                 instance,
                 sc_desc.surface,
                 &CanvasSwapChainDescriptor {
+                    label: sub_buffer_label(&desc.label, "swap chain"),
                     size: desc.size,
                     sample_count: desc.sample_count,
                     format: sc_desc.format,
+                    present_mode: sc_desc.present_mode,
                 },
-            )),
+            )?),
             None => None,
         };
 
         let mut color_buffers = Vec::with_capacity(desc.color_buffer_descriptors.len());
-        for cbd in desc.color_buffer_descriptors.iter() {
+        for (i, cbd) in desc.color_buffer_descriptors.iter().enumerate() {
             color_buffers.push(CanvasColorBuffer::new(
                 instance,
                 &CanvasColorBufferDescriptor {
+                    label: sub_buffer_label(&desc.label, &format!("color buffer {}", i)),
                     size: desc.size,
                     sample_count: desc.sample_count,
                     format: cbd.format,
@@ -495,30 +591,27 @@ impl CanvasBuffer {
             ));
         }
 
-        let depth_stencil_buffer = match &desc.depth_stencil_buffer_format {
-            Some(format) => Some(CanvasDepthStencilBuffer::new(
+        let depth_stencil_buffer = match &desc.depth_stencil_buffer_descriptor {
+            Some(dsbd) => Some(CanvasDepthStencilBuffer::new(
                 instance,
                 &CanvasDepthStencilBufferDescriptor {
+                    label: sub_buffer_label(&desc.label, "depth stencil buffer"),
                     size: desc.size,
                     sample_count: desc.sample_count,
-                    format: *format,
+                    format: dsbd.format,
+                    usage: dsbd.usage,
                 },
             )),
             None => None,
         };
 
-        assert!(
-            swap_chain.is_some() || !color_buffers.is_empty() || depth_stencil_buffer.is_some(),
-            "No buffer defined for a canvas buffer"
-        );
-
-        Self {
+        Ok(Self {
             size: desc.size,
             sample_count: desc.sample_count,
             swap_chain,
             color_buffers,
             depth_stencil_buffer,
-        }
+        })
     }
 
     pub fn size(&self) -> &CanvasSize {
@@ -542,9 +635,26 @@ impl CanvasBuffer {
     }
 
     pub fn current_frame(&mut self) -> Result<CanvasFrame, SwapChainError> {
-        let swap_chain = match &mut self.swap_chain {
-            Some(swap_chain) => Some(swap_chain.reference()?),
-            None => None,
+        let swap_chain_frame = self.next_swap_chain_frame()?;
+        Ok(self.frame_from_swap_chain_frame(swap_chain_frame))
+    }
+
+    pub(crate) fn next_swap_chain_frame(
+        &mut self,
+    ) -> Result<Option<SwapChainFrame>, SwapChainError> {
+        match &mut self.swap_chain {
+            Some(swap_chain) => Ok(Some(swap_chain.next_frame()?)),
+            None => Ok(None),
+        }
+    }
+
+    pub(crate) fn frame_from_swap_chain_frame(
+        &self,
+        swap_chain_frame: Option<SwapChainFrame>,
+    ) -> CanvasFrame {
+        let swap_chain = match (&self.swap_chain, swap_chain_frame) {
+            (Some(swap_chain), Some(frame)) => Some(swap_chain.reference_from_frame(frame)),
+            _ => None,
         };
 
         let mut color_buffers = Vec::with_capacity(self.color_buffers.len());
@@ -557,20 +667,85 @@ impl CanvasBuffer {
             None => None,
         };
 
-        Ok(CanvasFrame {
+        CanvasFrame {
             swap_chain,
             color_buffers,
             depth_stencil_buffer,
-        })
+        }
     }
 }
 
+fn sub_buffer_label(canvas_label: &Option<String>, buffer_name: &str) -> Option<String> {
+    canvas_label
+        .as_ref()
+        .map(|label| format!("{} {}", label, buffer_name))
+}
+
 pub trait Canvas {
-    fn current_frame(&mut self) -> Result<CanvasFrame, SwapChainError>;
+    fn current_frame(&mut self, instance: &Instance) -> Result<CanvasFrame, CanvasFrameError>;
     fn canvas_size(&self) -> &CanvasSize;
     fn sample_count(&self) -> SampleCount;
 }
 
+#[derive(Debug, Clone, PartialEq)]
+pub enum CanvasBufferCreationError {
+    NoBuffer,
+    UnpresentableSwapChainFormat(CanvasColorBufferFormat),
+}
+
+impl std::fmt::Display for CanvasBufferCreationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CanvasBufferCreationError::NoBuffer => {
+                write!(f, "No buffer defined for a canvas buffer")
+            }
+            CanvasBufferCreationError::UnpresentableSwapChainFormat(format) => {
+                write!(f, "The swap chain format can't be presented ({:?})", format)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CanvasBufferCreationError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CanvasFrameError {
+    SwapChainFailed(SwapChainError),
+    CanvasBufferCreationFailed(CanvasBufferCreationError),
+}
+
+impl std::fmt::Display for CanvasFrameError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CanvasFrameError::SwapChainFailed(e) => write!(f, "Swap chain failed ({})", e),
+            CanvasFrameError::CanvasBufferCreationFailed(e) => {
+                write!(f, "Canvas buffer creation failed ({})", e)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CanvasFrameError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CanvasFrameError::SwapChainFailed(e) => Some(e),
+            CanvasFrameError::CanvasBufferCreationFailed(e) => Some(e),
+        }
+    }
+}
+
+impl From<SwapChainError> for CanvasFrameError {
+    fn from(e: SwapChainError) -> Self {
+        CanvasFrameError::SwapChainFailed(e)
+    }
+}
+
+impl From<CanvasBufferCreationError> for CanvasFrameError {
+    fn from(e: CanvasBufferCreationError) -> Self {
+        CanvasFrameError::CanvasBufferCreationFailed(e)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -599,11 +774,14 @@ mod tests {
             &instance,
             &surface,
             &CanvasSwapChainDescriptor {
+                label: None,
                 sample_count: 2,
                 format: CanvasColorBufferFormat::Bgra8Unorm,
                 size: CanvasSize::new(12, 20),
+                present_mode: PresentMode::Fifo,
             },
-        );
+        )
+        .unwrap();
 
         expect_that!(&swap_chain.sample_count(), eq(2));
         expect_that!(
@@ -611,6 +789,7 @@ mod tests {
             eq(CanvasColorBufferFormat::Bgra8Unorm)
         );
         expect_that!(swap_chain.size(), eq(CanvasSize::new(12, 20)));
+        expect_that!(&swap_chain.requested_present_mode(), eq(PresentMode::Fifo));
 
         let reference = swap_chain.reference().unwrap();
         expect_that!(&reference.sample_count(), eq(2));
@@ -618,12 +797,76 @@ mod tests {
         expect_that!(reference.resolve_target().is_some());
     }
 
+    #[test]
+    fn canvas_swap_chain_unpresentable_format() {
+        let event_loop = EventLoop::<()>::new_any_thread();
+        let window = WindowBuilder::new()
+            .with_visible(false)
+            .build(&event_loop)
+            .unwrap();
+        let (instance, surface) = unsafe {
+            Instance::new_with_compatible_window(&InstanceDescriptor::default(), &window).unwrap()
+        };
+
+        let swap_chain = CanvasSwapChain::new(
+            &instance,
+            &surface,
+            &CanvasSwapChainDescriptor {
+                label: None,
+                sample_count: 1,
+                format: CanvasColorBufferFormat::Rgba16Float,
+                size: CanvasSize::new(12, 20),
+                present_mode: PresentMode::Fifo,
+            },
+        );
+        expect_that!(
+            &swap_chain.unwrap_err(),
+            eq(CanvasBufferCreationError::UnpresentableSwapChainFormat(
+                CanvasColorBufferFormat::Rgba16Float
+            ))
+        );
+    }
+
+    #[test]
+    fn color_buffer_format_presentable() {
+        expect_that!(CanvasColorBufferFormat::Bgra8UnormSrgb.is_presentable());
+        expect_that!(CanvasColorBufferFormat::Rgba8Unorm.is_presentable());
+        expect_that!(!CanvasColorBufferFormat::Rgba16Float.is_presentable());
+        expect_that!(!CanvasColorBufferFormat::R8Unorm.is_presentable());
+    }
+
+    #[test]
+    fn canvas_color_buffer_hdr() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        for format in &[
+            CanvasColorBufferFormat::Rgba16Float,
+            CanvasColorBufferFormat::Rgba32Float,
+            CanvasColorBufferFormat::Rgb10a2Unorm,
+            CanvasColorBufferFormat::R8Unorm,
+            CanvasColorBufferFormat::R32Float,
+        ] {
+            let buffer = CanvasColorBuffer::new(
+                &instance,
+                &CanvasColorBufferDescriptor {
+                    label: None,
+                    sample_count: 1,
+                    format: *format,
+                    size: CanvasSize::new(12, 20),
+                    usage: CanvasColorBufferUsage::SAMPLED,
+                },
+            );
+            expect_that!(&buffer.format(), eq(*format));
+            expect_that!(&buffer.texture().format(), eq(TextureFormat::from(*format)));
+        }
+    }
+
     #[test]
     fn canvas_color_buffer() {
         let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
         let buffer = CanvasColorBuffer::new(
             &instance,
             &CanvasColorBufferDescriptor {
+                label: None,
                 sample_count: 2,
                 format: CanvasColorBufferFormat::Bgra8Unorm,
                 size: CanvasSize::new(12, 20),
@@ -647,9 +890,11 @@ mod tests {
         let buffer = CanvasDepthStencilBuffer::new(
             &instance,
             &CanvasDepthStencilBufferDescriptor {
+                label: None,
                 sample_count: 2,
                 format: CanvasDepthStencilBufferFormat::Depth32Float,
                 size: CanvasSize::new(12, 20),
+                usage: CanvasDepthStencilBufferUsage::empty(),
             },
         );
 
@@ -682,11 +927,13 @@ mod tests {
         let mut buffer = CanvasBuffer::new(
             &instance,
             &CanvasBufferDescriptor {
+                label: None,
                 size: CanvasSize::new(12, 20),
                 sample_count: 2,
                 swap_chain_descriptor: Some(CanvasBufferSwapChainDescriptor {
                     surface: &surface,
                     format: CanvasColorBufferFormat::default(),
+                    present_mode: PresentMode::Mailbox,
                 }),
                 color_buffer_descriptors: vec![
                     CanvasBufferColorBufferDescriptor {
@@ -698,9 +945,13 @@ mod tests {
                         usage: CanvasColorBufferUsage::empty(),
                     },
                 ],
-                depth_stencil_buffer_format: Some(CanvasDepthStencilBufferFormat::Depth32Float),
+                depth_stencil_buffer_descriptor: Some(CanvasBufferDepthStencilBufferDescriptor {
+                    format: CanvasDepthStencilBufferFormat::Depth32Float,
+                    usage: CanvasDepthStencilBufferUsage::empty(),
+                }),
             },
-        );
+        )
+        .unwrap();
 
         expect_that!(buffer.size(), eq(CanvasSize::new(12, 20)));
         expect_that!(&buffer.sample_count(), eq(2));
@@ -748,18 +999,22 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "No buffer defined for a canvas buffer")]
     fn canvas_buffer_creation_error() {
         let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
-        let _buffer = CanvasBuffer::new(
+        let buffer = CanvasBuffer::new(
             &instance,
             &CanvasBufferDescriptor {
+                label: None,
                 size: CanvasSize::new(12, 20),
                 sample_count: 2,
                 swap_chain_descriptor: None,
                 color_buffer_descriptors: Vec::new(),
-                depth_stencil_buffer_format: None,
+                depth_stencil_buffer_descriptor: None,
             },
         );
+        expect_that!(
+            &buffer.unwrap_err(),
+            eq(CanvasBufferCreationError::NoBuffer)
+        );
     }
 }
@@ -1,60 +1,137 @@
 use std::default::Default;
 
 use super::{
-    Canvas, CanvasBuffer, CanvasBufferColorBufferDescriptor, CanvasBufferDescriptor,
-    CanvasColorBufferFormat, CanvasDepthStencilBufferFormat, CanvasFrame, CanvasSize, Instance,
-    SampleCount, Size, SwapChainError, Texture, TextureView,
+    Canvas, CanvasBuffer, CanvasBufferColorBufferDescriptor, CanvasBufferCreationError,
+    CanvasBufferDepthStencilBufferDescriptor, CanvasBufferDescriptor, CanvasColorBufferFormat,
+    CanvasDepthStencilBufferFormat, CanvasFrame, CanvasFrameError, CanvasSize, Instance,
+    RenderPassRequirements, SampleCount, Size, Texture, TextureView,
 };
 
 pub type CanvasTextureColorBufferDescriptor = CanvasBufferColorBufferDescriptor;
+pub type CanvasTextureDepthStencilBufferDescriptor = CanvasBufferDepthStencilBufferDescriptor;
 
 #[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CanvasTextureDescriptor {
+    pub label: Option<String>,
     pub size: Size<u32>,
     pub sample_count: SampleCount,
-    pub color_buffer_descriptor: Option<CanvasTextureColorBufferDescriptor>,
-    pub depth_stencil_buffer_format: Option<CanvasDepthStencilBufferFormat>,
+    pub color_buffer_descriptors: Vec<CanvasTextureColorBufferDescriptor>,
+    pub depth_stencil_buffer_descriptor: Option<CanvasTextureDepthStencilBufferDescriptor>,
 }
 
 impl Default for CanvasTextureDescriptor {
     fn default() -> Self {
         Self {
+            label: None,
             size: Size::new(1, 1),
             sample_count: 1,
-            color_buffer_descriptor: Some(CanvasTextureColorBufferDescriptor::default()),
-            depth_stencil_buffer_format: None,
+            color_buffer_descriptors: vec![CanvasTextureColorBufferDescriptor::default()],
+            depth_stencil_buffer_descriptor: None,
         }
     }
 }
 
 #[derive(Debug)]
 pub struct CanvasTexture {
+    label: Option<String>,
     canvas_buffer: CanvasBuffer,
+    color_buffer_descriptors: Vec<CanvasTextureColorBufferDescriptor>,
+    depth_stencil_buffer_descriptor: Option<CanvasTextureDepthStencilBufferDescriptor>,
+    generation: u64,
 }
 
 impl CanvasTexture {
-    pub fn new(instance: &Instance, desc: &CanvasTextureDescriptor) -> Self {
-        let canvas_buffer = CanvasBuffer::new(
+    pub fn new(
+        instance: &Instance,
+        desc: &CanvasTextureDescriptor,
+    ) -> Result<Self, CanvasBufferCreationError> {
+        let canvas_buffer = Self::create_buffer(
+            instance,
+            desc.label.clone(),
+            desc.size,
+            desc.sample_count,
+            desc.color_buffer_descriptors.clone(),
+            desc.depth_stencil_buffer_descriptor,
+        )?;
+        Ok(Self {
+            label: desc.label.clone(),
+            canvas_buffer,
+            color_buffer_descriptors: desc.color_buffer_descriptors.clone(),
+            depth_stencil_buffer_descriptor: desc.depth_stencil_buffer_descriptor,
+            generation: 0,
+        })
+    }
+
+    // Resizing replaces the underlying textures: views previously obtained
+    // from this canvas (and bind groups using them) keep referring to the old
+    // textures. The generation is increased every time this happens, so that
+    // dependents can detect it and recreate their resources.
+    pub fn resize(&mut self, instance: &Instance, size: CanvasSize) {
+        if *self.canvas_size() != size {
+            self.rebuild_buffer(instance, size);
+        }
+    }
+
+    // Recreates the underlying textures, e.g. after the instance has been
+    // recreated. Increases the generation as resizing does.
+    pub fn rebuild(&mut self, instance: &Instance) {
+        self.rebuild_buffer(instance, *self.canvas_size());
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    fn rebuild_buffer(&mut self, instance: &Instance, size: CanvasSize) {
+        self.canvas_buffer = Self::create_buffer(
+            instance,
+            self.label.clone(),
+            size,
+            self.sample_count(),
+            self.color_buffer_descriptors.clone(),
+            self.depth_stencil_buffer_descriptor,
+        )
+        .expect("The canvas texture buffer descriptor has already been validated");
+        self.generation += 1;
+    }
+
+    fn create_buffer(
+        instance: &Instance,
+        label: Option<String>,
+        size: CanvasSize,
+        sample_count: SampleCount,
+        color_buffer_descriptors: Vec<CanvasTextureColorBufferDescriptor>,
+        depth_stencil_buffer_descriptor: Option<CanvasTextureDepthStencilBufferDescriptor>,
+    ) -> Result<CanvasBuffer, CanvasBufferCreationError> {
+        CanvasBuffer::new(
             instance,
             &CanvasBufferDescriptor {
-                size: desc.size,
-                sample_count: desc.sample_count,
+                label,
+                size,
+                sample_count,
                 swap_chain_descriptor: None,
-                color_buffer_descriptors: match desc.color_buffer_descriptor {
-                    Some(v) => vec![v],
-                    None => Vec::new(),
-                },
-                depth_stencil_buffer_format: desc.depth_stencil_buffer_format,
+                color_buffer_descriptors,
+                depth_stencil_buffer_descriptor,
             },
-        );
-        Self { canvas_buffer }
+        )
     }
 
-    pub fn color_buffer_format(&self) -> Option<CanvasColorBufferFormat> {
-        if self.canvas_buffer.color_buffers().is_empty() {
-            None
-        } else {
-            Some(self.canvas_buffer.color_buffers()[0].format())
+    pub fn color_buffer_count(&self) -> usize {
+        self.canvas_buffer.color_buffers().len()
+    }
+
+    pub fn color_buffer_formats(&self) -> Vec<CanvasColorBufferFormat> {
+        self.canvas_buffer
+            .color_buffers()
+            .iter()
+            .map(|x| x.format())
+            .collect()
+    }
+
+    pub fn color_buffer_format(&self, index: usize) -> Option<CanvasColorBufferFormat> {
+        match self.canvas_buffer.color_buffers().get(index) {
+            Some(v) => Some(v.format()),
+            None => None,
         }
     }
 
@@ -65,11 +142,10 @@ impl CanvasTexture {
         }
     }
 
-    pub fn color_texture_view(&self) -> Option<&TextureView> {
-        if self.canvas_buffer.color_buffers().is_empty() {
-            None
-        } else {
-            Some(self.canvas_buffer.color_buffers()[0].texture_view())
+    pub fn color_texture_view(&self, index: usize) -> Option<&TextureView> {
+        match self.canvas_buffer.color_buffers().get(index) {
+            Some(v) => Some(v.texture_view()),
+            None => None,
         }
     }
 
@@ -80,11 +156,10 @@ impl CanvasTexture {
         }
     }
 
-    pub fn color_texture(&self) -> Option<&Texture> {
-        if self.canvas_buffer.color_buffers().is_empty() {
-            None
-        } else {
-            Some(self.canvas_buffer.color_buffers()[0].texture())
+    pub fn color_texture(&self, index: usize) -> Option<&Texture> {
+        match self.canvas_buffer.color_buffers().get(index) {
+            Some(v) => Some(v.texture()),
+            None => None,
         }
     }
 
@@ -94,11 +169,20 @@ impl CanvasTexture {
             None => None,
         }
     }
+
+    // Requirements for a render pass writing to all the buffers of the canvas.
+    pub fn render_pass_requirements(&self) -> RenderPassRequirements {
+        RenderPassRequirements {
+            sample_count: self.sample_count(),
+            color_buffer_formats: self.color_buffer_formats(),
+            depth_stencil_buffer_format: self.depth_stencil_buffer_format(),
+        }
+    }
 }
 
 impl Canvas for CanvasTexture {
-    fn current_frame(&mut self) -> Result<CanvasFrame, SwapChainError> {
-        self.canvas_buffer.current_frame()
+    fn current_frame(&mut self, _instance: &Instance) -> Result<CanvasFrame, CanvasFrameError> {
+        Ok(self.canvas_buffer.current_frame()?)
     }
 
     fn canvas_size(&self) -> &CanvasSize {
@@ -116,22 +200,27 @@ mod tests {
 
     use galvanic_assert::{matchers::*, *};
 
-    use crate::core::InstanceDescriptor;
+    use crate::core::{
+        CanvasColorBufferUsage, CanvasDepthStencilBufferUsage, ColorF64, ColorOperations,
+        CommandSequence, DepthOperations, Extent3d, InstanceDescriptor, LoadOp,
+        RenderPassOperations, TextureUsage,
+    };
 
     #[test]
     fn default_parameters() {
         let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
-        let mut texture = CanvasTexture::new(&instance, &CanvasTextureDescriptor::default());
+        let mut texture =
+            CanvasTexture::new(&instance, &CanvasTextureDescriptor::default()).unwrap();
 
         expect_that!(texture.canvas_size(), eq(CanvasSize::new(1, 1)));
         expect_that!(&texture.sample_count(), eq(1));
         expect_that!(
-            &texture.color_buffer_format(),
+            &texture.color_buffer_format(0),
             eq(Some(CanvasColorBufferFormat::default()))
         );
         expect_that!(&texture.depth_stencil_buffer_format(), eq(None));
 
-        let frame = texture.current_frame().unwrap();
+        let frame = texture.current_frame(&instance).unwrap();
         expect_that!(frame.swap_chain().is_none());
         expect_that!(&frame.color_buffers().len(), eq(1));
         expect_that!(frame.depth_stencil_buffer().is_none());
@@ -153,17 +242,18 @@ mod tests {
                 size: CanvasSize::new(20, 30),
                 ..CanvasTextureDescriptor::default()
             },
-        );
+        )
+        .unwrap();
 
         expect_that!(texture.canvas_size(), eq(CanvasSize::new(20, 30)));
         expect_that!(&texture.sample_count(), eq(1));
         expect_that!(
-            &texture.color_buffer_format(),
+            &texture.color_buffer_format(0),
             eq(Some(CanvasColorBufferFormat::default()))
         );
         expect_that!(&texture.depth_stencil_buffer_format(), eq(None));
 
-        let frame = texture.current_frame().unwrap();
+        let frame = texture.current_frame(&instance).unwrap();
         expect_that!(frame.swap_chain().is_none());
         expect_that!(&frame.color_buffers().len(), eq(1));
         expect_that!(frame.depth_stencil_buffer().is_none());
@@ -185,17 +275,18 @@ mod tests {
                 sample_count: 2,
                 ..CanvasTextureDescriptor::default()
             },
-        );
+        )
+        .unwrap();
 
         expect_that!(texture.canvas_size(), eq(CanvasSize::new(1, 1)));
         expect_that!(&texture.sample_count(), eq(2));
         expect_that!(
-            &texture.color_buffer_format(),
+            &texture.color_buffer_format(0),
             eq(Some(CanvasColorBufferFormat::default()))
         );
         expect_that!(&texture.depth_stencil_buffer_format(), eq(None));
 
-        let frame = texture.current_frame().unwrap();
+        let frame = texture.current_frame(&instance).unwrap();
         expect_that!(frame.swap_chain().is_none());
         expect_that!(&frame.color_buffers().len(), eq(1));
         expect_that!(frame.depth_stencil_buffer().is_none());
@@ -214,15 +305,19 @@ mod tests {
         let mut texture = CanvasTexture::new(
             &instance,
             &CanvasTextureDescriptor {
-                depth_stencil_buffer_format: Some(CanvasDepthStencilBufferFormat::Depth24Plus),
+                depth_stencil_buffer_descriptor: Some(CanvasTextureDepthStencilBufferDescriptor {
+                    format: CanvasDepthStencilBufferFormat::Depth24Plus,
+                    usage: CanvasDepthStencilBufferUsage::empty(),
+                }),
                 ..CanvasTextureDescriptor::default()
             },
-        );
+        )
+        .unwrap();
 
         expect_that!(texture.canvas_size(), eq(CanvasSize::new(1, 1)));
         expect_that!(&texture.sample_count(), eq(1));
         expect_that!(
-            &texture.color_buffer_format(),
+            &texture.color_buffer_format(0),
             eq(Some(CanvasColorBufferFormat::default()))
         );
         expect_that!(
@@ -230,7 +325,7 @@ mod tests {
             eq(Some(CanvasDepthStencilBufferFormat::Depth24Plus))
         );
 
-        let frame = texture.current_frame().unwrap();
+        let frame = texture.current_frame(&instance).unwrap();
         expect_that!(frame.swap_chain().is_none());
         expect_that!(&frame.color_buffers().len(), eq(1));
         expect_that!(frame.depth_stencil_buffer().is_some());
@@ -256,21 +351,25 @@ mod tests {
         let mut texture = CanvasTexture::new(
             &instance,
             &CanvasTextureDescriptor {
-                color_buffer_descriptor: None,
-                depth_stencil_buffer_format: Some(CanvasDepthStencilBufferFormat::Depth24Plus),
+                color_buffer_descriptors: Vec::new(),
+                depth_stencil_buffer_descriptor: Some(CanvasTextureDepthStencilBufferDescriptor {
+                    format: CanvasDepthStencilBufferFormat::Depth24Plus,
+                    usage: CanvasDepthStencilBufferUsage::empty(),
+                }),
                 ..CanvasTextureDescriptor::default()
             },
-        );
+        )
+        .unwrap();
 
         expect_that!(texture.canvas_size(), eq(CanvasSize::new(1, 1)));
         expect_that!(&texture.sample_count(), eq(1));
-        expect_that!(&texture.color_buffer_format(), eq(None));
+        expect_that!(&texture.color_buffer_format(0), eq(None));
         expect_that!(
             &texture.depth_stencil_buffer_format(),
             eq(Some(CanvasDepthStencilBufferFormat::Depth24Plus))
         );
 
-        let frame = texture.current_frame().unwrap();
+        let frame = texture.current_frame(&instance).unwrap();
         expect_that!(frame.swap_chain().is_none());
         expect_that!(&frame.color_buffers().is_empty());
         expect_that!(frame.depth_stencil_buffer().is_some());
@@ -290,15 +389,19 @@ mod tests {
             &instance,
             &CanvasTextureDescriptor {
                 sample_count: 2,
-                depth_stencil_buffer_format: Some(CanvasDepthStencilBufferFormat::Depth24Plus),
+                depth_stencil_buffer_descriptor: Some(CanvasTextureDepthStencilBufferDescriptor {
+                    format: CanvasDepthStencilBufferFormat::Depth24Plus,
+                    usage: CanvasDepthStencilBufferUsage::empty(),
+                }),
                 ..CanvasTextureDescriptor::default()
             },
-        );
+        )
+        .unwrap();
 
         expect_that!(texture.canvas_size(), eq(CanvasSize::new(1, 1)));
         expect_that!(&texture.sample_count(), eq(2));
         expect_that!(
-            &texture.color_buffer_format(),
+            &texture.color_buffer_format(0),
             eq(Some(CanvasColorBufferFormat::default()))
         );
         expect_that!(
@@ -306,7 +409,7 @@ mod tests {
             eq(Some(CanvasDepthStencilBufferFormat::Depth24Plus))
         );
 
-        let frame = texture.current_frame().unwrap();
+        let frame = texture.current_frame(&instance).unwrap();
         expect_that!(frame.swap_chain().is_none());
         expect_that!(&frame.color_buffers().len(), eq(1));
         expect_that!(frame.depth_stencil_buffer().is_some());
@@ -327,16 +430,268 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "No buffer defined for a canvas buffer")]
+    fn multiple_color_buffers() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let mut texture = CanvasTexture::new(
+            &instance,
+            &CanvasTextureDescriptor {
+                label: None,
+                size: CanvasSize::new(20, 30),
+                sample_count: 2,
+                color_buffer_descriptors: vec![
+                    CanvasTextureColorBufferDescriptor {
+                        format: CanvasColorBufferFormat::Rgba8UnormSrgb,
+                        usage: CanvasColorBufferUsage::SAMPLED,
+                    },
+                    CanvasTextureColorBufferDescriptor {
+                        format: CanvasColorBufferFormat::Rgba8Unorm,
+                        usage: CanvasColorBufferUsage::SAMPLED,
+                    },
+                    CanvasTextureColorBufferDescriptor {
+                        format: CanvasColorBufferFormat::Bgra8Unorm,
+                        usage: CanvasColorBufferUsage::COPY_SRC,
+                    },
+                ],
+                depth_stencil_buffer_descriptor: Some(CanvasTextureDepthStencilBufferDescriptor {
+                    format: CanvasDepthStencilBufferFormat::Depth32Float,
+                    usage: CanvasDepthStencilBufferUsage::empty(),
+                }),
+            },
+        )
+        .unwrap();
+
+        expect_that!(&texture.color_buffer_count(), eq(3));
+        expect_that!(
+            &texture.color_buffer_format(1),
+            eq(Some(CanvasColorBufferFormat::Rgba8Unorm))
+        );
+        expect_that!(&texture.color_buffer_format(3), eq(None));
+        expect_that!(texture.color_texture_view(2).is_some());
+        expect_that!(texture.color_texture_view(3).is_none());
+        expect_that!(texture
+            .color_texture(2)
+            .unwrap()
+            .usage()
+            .contains(TextureUsage::COPY_SRC));
+        expect_that!(texture.color_texture(3).is_none());
+
+        let requirements = texture.render_pass_requirements();
+        expect_that!(
+            &requirements,
+            eq(RenderPassRequirements {
+                sample_count: 2,
+                color_buffer_formats: vec![
+                    CanvasColorBufferFormat::Rgba8UnormSrgb,
+                    CanvasColorBufferFormat::Rgba8Unorm,
+                    CanvasColorBufferFormat::Bgra8Unorm,
+                ],
+                depth_stencil_buffer_format: Some(CanvasDepthStencilBufferFormat::Depth32Float),
+            })
+        );
+
+        let mut cmd_seq = CommandSequence::new(&instance);
+        {
+            let frame = texture.current_frame(&instance).unwrap();
+            expect_that!(&frame.color_buffers().len(), eq(3));
+            let _rpass = cmd_seq
+                .begin_render_pass(&frame, &requirements, &RenderPassOperations::default())
+                .unwrap();
+        }
+        cmd_seq.submit(&instance);
+    }
+
+    #[test]
+    fn hdr_color_buffer() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let mut texture = CanvasTexture::new(
+            &instance,
+            &CanvasTextureDescriptor {
+                size: CanvasSize::new(2, 2),
+                color_buffer_descriptors: vec![CanvasTextureColorBufferDescriptor {
+                    format: CanvasColorBufferFormat::Rgba16Float,
+                    usage: CanvasColorBufferUsage::COPY_SRC,
+                }],
+                ..CanvasTextureDescriptor::default()
+            },
+        )
+        .unwrap();
+
+        let requirements = texture.render_pass_requirements();
+        let mut cmd_seq = CommandSequence::new(&instance);
+        {
+            let frame = texture.current_frame(&instance).unwrap();
+            let _rpass = cmd_seq
+                .begin_render_pass(
+                    &frame,
+                    &requirements,
+                    &RenderPassOperations {
+                        color_operations: vec![ColorOperations {
+                            load: LoadOp::Clear(ColorF64 {
+                                r: 4.,
+                                g: 0.5,
+                                b: 0.,
+                                a: 1.,
+                            }),
+                            store: true,
+                        }],
+                        ..RenderPassOperations::default()
+                    },
+                )
+                .unwrap();
+        }
+        cmd_seq.submit(&instance);
+
+        let image = texture
+            .color_texture(0)
+            .unwrap()
+            .to_rgba32f_image(&instance, None);
+        for pixel in image.pixels() {
+            expect_that!(&pixel.0, eq([4., 0.5, 0., 1.]));
+        }
+    }
+
+    #[test]
+    fn resize() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let mut texture = CanvasTexture::new(
+            &instance,
+            &CanvasTextureDescriptor {
+                label: Some(String::from("offscreen canvas")),
+                size: CanvasSize::new(20, 30),
+                sample_count: 2,
+                color_buffer_descriptors: vec![CanvasTextureColorBufferDescriptor {
+                    format: CanvasColorBufferFormat::Bgra8Unorm,
+                    usage: CanvasColorBufferUsage::COPY_SRC,
+                }],
+                depth_stencil_buffer_descriptor: Some(CanvasTextureDepthStencilBufferDescriptor {
+                    format: CanvasDepthStencilBufferFormat::Depth32Float,
+                    usage: CanvasDepthStencilBufferUsage::empty(),
+                }),
+            },
+        )
+        .unwrap();
+        expect_that!(&texture.generation(), eq(0));
+
+        texture.resize(&instance, CanvasSize::new(20, 30));
+        expect_that!(&texture.generation(), eq(0));
+
+        texture.resize(&instance, CanvasSize::new(40, 10));
+        expect_that!(&texture.generation(), eq(1));
+        expect_that!(texture.canvas_size(), eq(CanvasSize::new(40, 10)));
+        expect_that!(&texture.sample_count(), eq(2));
+        expect_that!(
+            &texture.color_buffer_format(0),
+            eq(Some(CanvasColorBufferFormat::Bgra8Unorm))
+        );
+        expect_that!(
+            &texture.depth_stencil_buffer_format(),
+            eq(Some(CanvasDepthStencilBufferFormat::Depth32Float))
+        );
+        expect_that!(
+            texture.color_texture(0).unwrap().size(),
+            eq(Extent3d {
+                width: 40,
+                height: 10,
+                depth: 1
+            })
+        );
+        expect_that!(texture
+            .color_texture(0)
+            .unwrap()
+            .usage()
+            .contains(TextureUsage::COPY_SRC));
+
+        let frame = texture.current_frame(&instance).unwrap();
+        expect_that!(&frame.color_buffers().len(), eq(1));
+        expect_that!(frame.depth_stencil_buffer().is_some());
+    }
+
+    #[test]
+    fn rebuild() {
+        let mut instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let mut texture = CanvasTexture::new(
+            &instance,
+            &CanvasTextureDescriptor {
+                size: CanvasSize::new(20, 30),
+                ..CanvasTextureDescriptor::default()
+            },
+        )
+        .unwrap();
+
+        instance.recreate(None).unwrap();
+        texture.rebuild(&instance);
+        expect_that!(&texture.generation(), eq(1));
+        expect_that!(texture.canvas_size(), eq(CanvasSize::new(20, 30)));
+        let _frame = texture.current_frame(&instance).unwrap();
+    }
+
+    #[test]
+    fn depth_buffer_readback() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let mut texture = CanvasTexture::new(
+            &instance,
+            &CanvasTextureDescriptor {
+                size: CanvasSize::new(4, 3),
+                color_buffer_descriptors: Vec::new(),
+                depth_stencil_buffer_descriptor: Some(CanvasTextureDepthStencilBufferDescriptor {
+                    format: CanvasDepthStencilBufferFormat::Depth32Float,
+                    usage: CanvasDepthStencilBufferUsage::SAMPLED
+                        | CanvasDepthStencilBufferUsage::COPY_SRC,
+                }),
+                ..CanvasTextureDescriptor::default()
+            },
+        )
+        .unwrap();
+        expect_that!(texture
+            .depth_stencil_texture()
+            .unwrap()
+            .usage()
+            .contains(TextureUsage::SAMPLED | TextureUsage::COPY_SRC));
+
+        let requirements = texture.render_pass_requirements();
+        let mut cmd_seq = CommandSequence::new(&instance);
+        {
+            let frame = texture.current_frame(&instance).unwrap();
+            let _rpass = cmd_seq
+                .begin_render_pass(
+                    &frame,
+                    &requirements,
+                    &RenderPassOperations {
+                        depth_operations: Some(DepthOperations {
+                            load: LoadOp::Clear(0.25),
+                            store: true,
+                        }),
+                        ..RenderPassOperations::default()
+                    },
+                )
+                .unwrap();
+        }
+        cmd_seq.submit(&instance);
+
+        let image = texture
+            .depth_stencil_texture()
+            .unwrap()
+            .to_depth_image(&instance);
+        expect_that!(&image.dimensions(), eq((4, 3)));
+        for pixel in image.pixels() {
+            expect_that!(&pixel.0[0], eq(0.25));
+        }
+    }
+
+    #[test]
     fn no_buffer_error() {
         let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
-        let _texture = CanvasTexture::new(
+        let texture = CanvasTexture::new(
             &instance,
             &CanvasTextureDescriptor {
-                color_buffer_descriptor: None,
-                depth_stencil_buffer_format: None,
+                color_buffer_descriptors: Vec::new(),
+                depth_stencil_buffer_descriptor: None,
                 ..CanvasTextureDescriptor::default()
             },
         );
+        expect_that!(
+            &texture.unwrap_err(),
+            eq(CanvasBufferCreationError::NoBuffer)
+        );
     }
 }
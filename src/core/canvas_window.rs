@@ -7,31 +7,51 @@ use rae_app::{
 };
 
 use super::{
-    Canvas, CanvasBuffer, CanvasBufferDescriptor, CanvasBufferSwapChainDescriptor,
-    CanvasColorBufferFormat, CanvasDepthStencilBufferFormat, CanvasFrame, CanvasSize, Instance,
-    SampleCount, Surface, SwapChainError,
+    Canvas, CanvasBuffer, CanvasBufferCreationError, CanvasBufferDepthStencilBufferDescriptor,
+    CanvasBufferDescriptor, CanvasBufferSwapChainDescriptor, CanvasColorBufferFormat,
+    CanvasDepthStencilBufferFormat, CanvasDepthStencilBufferUsage, CanvasFrame, CanvasFrameError,
+    CanvasSize, Instance, PresentMode, SampleCount, Surface, SwapChainError,
 };
 
+// The depth stencil buffer of a window is only used as a render attachment.
+fn depth_stencil_buffer_descriptor(
+    format: Option<CanvasDepthStencilBufferFormat>,
+) -> Option<CanvasBufferDepthStencilBufferDescriptor> {
+    match format {
+        Some(format) => Some(CanvasBufferDepthStencilBufferDescriptor {
+            format,
+            usage: CanvasDepthStencilBufferUsage::empty(),
+        }),
+        None => None,
+    }
+}
+
 #[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
 pub struct CanvasWindowDescriptor {
+    pub label: Option<String>,
     pub sample_count: SampleCount,
     pub color_buffer_format: CanvasColorBufferFormat,
     pub depth_stencil_buffer_format: Option<CanvasDepthStencilBufferFormat>,
+    pub present_mode: PresentMode,
 }
 
 impl Default for CanvasWindowDescriptor {
     fn default() -> Self {
         Self {
+            label: None,
             sample_count: 1,
             color_buffer_format: CanvasColorBufferFormat::default(),
             depth_stencil_buffer_format: None,
+            present_mode: PresentMode::Mailbox,
         }
     }
 }
 
 #[derive(Debug)]
 pub struct CanvasWindow {
+    label: Option<String>,
     canvas_buffer: CanvasBuffer,
+    generation: u64,
     surface: Surface,
     window: Window,
 }
@@ -42,9 +62,9 @@ impl CanvasWindow {
         instance: &Instance,
         event_loop: &EventLoop<T>,
         desc: &CanvasWindowDescriptor,
-    ) -> Result<Self, OsError> {
+    ) -> Result<Self, CanvasWindowCreationError> {
         let window = Window::new(event_loop)?;
-        Ok(Self::from_window(instance, window, desc))
+        Ok(Self::from_window(instance, window, desc)?)
     }
 
     // Unsafe: surface creation.
@@ -52,7 +72,7 @@ impl CanvasWindow {
         instance: &Instance,
         window: Window,
         desc: &CanvasWindowDescriptor,
-    ) -> Self {
+    ) -> Result<Self, CanvasBufferCreationError> {
         let surface = Surface::new(&instance, &window);
         Self::from_window_and_surface(instance, window, surface, desc)
     }
@@ -63,26 +83,32 @@ impl CanvasWindow {
         window: Window,
         surface: Surface,
         desc: &CanvasWindowDescriptor,
-    ) -> Self {
+    ) -> Result<Self, CanvasBufferCreationError> {
         let surface_size = window.inner_size();
         let canvas_buffer = CanvasBuffer::new(
             instance,
             &CanvasBufferDescriptor {
+                label: desc.label.clone(),
                 size: CanvasSize::new(surface_size.width, surface_size.height),
                 sample_count: desc.sample_count,
                 swap_chain_descriptor: Some(CanvasBufferSwapChainDescriptor {
                     surface: &surface,
                     format: desc.color_buffer_format,
+                    present_mode: desc.present_mode,
                 }),
                 color_buffer_descriptors: Vec::new(),
-                depth_stencil_buffer_format: desc.depth_stencil_buffer_format,
+                depth_stencil_buffer_descriptor: depth_stencil_buffer_descriptor(
+                    desc.depth_stencil_buffer_format,
+                ),
             },
-        );
-        Self {
+        )?;
+        Ok(Self {
+            label: desc.label.clone(),
             canvas_buffer,
+            generation: 0,
             surface,
             window,
-        }
+        })
     }
 
     pub fn color_buffer_format(&self) -> CanvasColorBufferFormat {
@@ -96,24 +122,73 @@ impl CanvasWindow {
         }
     }
 
-    pub fn update_buffer(&mut self, instance: &Instance) {
+    // wgpu 0.6 can't query the mode in use: if the surface doesn't support
+    // the requested mode, the backend silently falls back to PresentMode::Fifo.
+    pub fn requested_present_mode(&self) -> PresentMode {
+        self.canvas_buffer
+            .swap_chain()
+            .unwrap()
+            .requested_present_mode()
+    }
+
+    // Number of times the swap chain and buffers have been rebuilt.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    pub fn set_present_mode(
+        &mut self,
+        instance: &Instance,
+        present_mode: PresentMode,
+    ) -> Result<(), CanvasBufferCreationError> {
+        if self.requested_present_mode() != present_mode {
+            self.rebuild_buffer(instance, present_mode)?;
+        }
+        Ok(())
+    }
+
+    // Recreates the swap chain and buffers, e.g. after the instance has been
+    // recreated.
+    pub fn rebuild(&mut self, instance: &Instance) -> Result<(), CanvasBufferCreationError> {
+        self.rebuild_buffer(instance, self.requested_present_mode())
+    }
+
+    pub fn update_buffer(&mut self, instance: &Instance) -> Result<(), CanvasBufferCreationError> {
         let current_size = self.inner_size();
         let current_size = CanvasSize::new(current_size.width, current_size.height);
         if *self.canvas_size() != current_size {
-            self.canvas_buffer = CanvasBuffer::new(
-                instance,
-                &CanvasBufferDescriptor {
-                    size: current_size,
-                    sample_count: self.sample_count(),
-                    swap_chain_descriptor: Some(CanvasBufferSwapChainDescriptor {
-                        surface: &self.surface,
-                        format: self.color_buffer_format(),
-                    }),
-                    color_buffer_descriptors: Vec::new(),
-                    depth_stencil_buffer_format: self.depth_stencil_buffer_format(),
-                },
-            );
+            self.rebuild_buffer(instance, self.requested_present_mode())?;
         }
+        Ok(())
+    }
+
+    // On failure the previous buffer is kept.
+    fn rebuild_buffer(
+        &mut self,
+        instance: &Instance,
+        present_mode: PresentMode,
+    ) -> Result<(), CanvasBufferCreationError> {
+        let current_size = self.inner_size();
+        let current_size = CanvasSize::new(current_size.width, current_size.height);
+        self.canvas_buffer = CanvasBuffer::new(
+            instance,
+            &CanvasBufferDescriptor {
+                label: self.label.clone(),
+                size: current_size,
+                sample_count: self.sample_count(),
+                swap_chain_descriptor: Some(CanvasBufferSwapChainDescriptor {
+                    surface: &self.surface,
+                    format: self.color_buffer_format(),
+                    present_mode,
+                }),
+                color_buffer_descriptors: Vec::new(),
+                depth_stencil_buffer_descriptor: depth_stencil_buffer_descriptor(
+                    self.depth_stencil_buffer_format(),
+                ),
+            },
+        )?;
+        self.generation += 1;
+        Ok(())
     }
 
     pub fn id(&self) -> WindowId {
@@ -151,12 +226,16 @@ impl CanvasWindow {
         self.window.outer_size()
     }
 
-    pub fn set_inner_size<S>(&mut self, instance: &Instance, size: S)
+    pub fn set_inner_size<S>(
+        &mut self,
+        instance: &Instance,
+        size: S,
+    ) -> Result<(), CanvasBufferCreationError>
     where
         S: Into<window::Size>,
     {
         self.window.set_inner_size(size);
-        self.update_buffer(instance);
+        self.update_buffer(instance)
     }
 
     pub fn set_min_inner_size<S>(&mut self, min_size: Option<S>)
@@ -241,8 +320,26 @@ impl CanvasWindow {
 }
 
 impl Canvas for CanvasWindow {
-    fn current_frame(&mut self) -> Result<CanvasFrame, SwapChainError> {
-        self.canvas_buffer.current_frame()
+    fn current_frame(&mut self, instance: &Instance) -> Result<CanvasFrame, CanvasFrameError> {
+        // Not every backend reports a swap chain as outdated after a resize.
+        self.update_buffer(instance)?;
+        // An outdated or lost swap chain can be recovered by recreating it.
+        let swap_chain_frame = match self.canvas_buffer.next_swap_chain_frame() {
+            Err(SwapChainError::Outdated) | Err(SwapChainError::Lost) => {
+                self.rebuild_buffer(instance, self.requested_present_mode())?;
+                self.canvas_buffer.next_swap_chain_frame()
+            }
+            result => result,
+        };
+        match swap_chain_frame {
+            Ok(frame) => Ok(self.canvas_buffer.frame_from_swap_chain_frame(frame)),
+            Err(e) => {
+                if let SwapChainError::Lost | SwapChainError::OutOfMemory = e {
+                    instance.mark_device_lost();
+                }
+                Err(e.into())
+            }
+        }
     }
 
     fn canvas_size(&self) -> &CanvasSize {
@@ -254,6 +351,46 @@ impl Canvas for CanvasWindow {
     }
 }
 
+#[derive(Debug)]
+pub enum CanvasWindowCreationError {
+    WindowCreationFailed(OsError),
+    CanvasBufferCreationFailed(CanvasBufferCreationError),
+}
+
+impl std::fmt::Display for CanvasWindowCreationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CanvasWindowCreationError::WindowCreationFailed(e) => {
+                write!(f, "Window creation failed ({})", e)
+            }
+            CanvasWindowCreationError::CanvasBufferCreationFailed(e) => {
+                write!(f, "Canvas buffer creation failed ({})", e)
+            }
+        }
+    }
+}
+
+impl std::error::Error for CanvasWindowCreationError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            CanvasWindowCreationError::WindowCreationFailed(e) => Some(e),
+            CanvasWindowCreationError::CanvasBufferCreationFailed(e) => Some(e),
+        }
+    }
+}
+
+impl From<OsError> for CanvasWindowCreationError {
+    fn from(e: OsError) -> Self {
+        CanvasWindowCreationError::WindowCreationFailed(e)
+    }
+}
+
+impl From<CanvasBufferCreationError> for CanvasWindowCreationError {
+    fn from(e: CanvasBufferCreationError) -> Self {
+        CanvasWindowCreationError::CanvasBufferCreationFailed(e)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -275,7 +412,7 @@ mod tests {
             .with_visible(false)
             .build(&event_loop)
             .unwrap();
-        let window = unsafe { CanvasWindow::from_window(&instance, window, desc) };
+        let window = unsafe { CanvasWindow::from_window(&instance, window, desc).unwrap() };
         (window, instance)
     }
 
@@ -289,6 +426,7 @@ mod tests {
             .unwrap();
         let _canvas_window = unsafe {
             CanvasWindow::from_window(&instance, window, &CanvasWindowDescriptor::default())
+                .unwrap()
         };
     }
 
@@ -309,6 +447,7 @@ mod tests {
                 surface,
                 &CanvasWindowDescriptor::default(),
             )
+            .unwrap()
         };
     }
 
@@ -325,6 +464,7 @@ mod tests {
                     .unwrap(),
                 &CanvasWindowDescriptor::default(),
             )
+            .unwrap()
         };
         let window2 = unsafe {
             CanvasWindow::from_window(
@@ -335,6 +475,7 @@ mod tests {
                     .unwrap(),
                 &CanvasWindowDescriptor::default(),
             )
+            .unwrap()
         };
         expect_that!(&window1.id(), not(eq(window2.id())));
     }
@@ -356,6 +497,7 @@ mod tests {
                 surface,
                 &CanvasWindowDescriptor::default(),
             )
+            .unwrap()
         };
         let window2 = unsafe {
             CanvasWindow::from_window(
@@ -366,6 +508,7 @@ mod tests {
                     .unwrap(),
                 &CanvasWindowDescriptor::default(),
             )
+            .unwrap()
         };
         expect_that!(&window1.id(), not(eq(window2.id())));
     }
@@ -430,13 +573,59 @@ mod tests {
             height: 100,
         });
         expect_that!(window.canvas_size(), eq(CanvasSize::new(150, 30)));
-        window.update_buffer(&instance);
+        window.update_buffer(&instance).unwrap();
+        expect_that!(window.canvas_size(), eq(CanvasSize::new(200, 100)));
+    }
+
+    #[test]
+    fn set_present_mode() {
+        let (mut window, instance) = create_window(
+            window::PhysicalSize {
+                width: 150,
+                height: 30,
+            },
+            &CanvasWindowDescriptor {
+                present_mode: PresentMode::Fifo,
+                ..CanvasWindowDescriptor::default()
+            },
+        );
+        expect_that!(&window.requested_present_mode(), eq(PresentMode::Fifo));
+        window
+            .set_present_mode(&instance, PresentMode::Immediate)
+            .unwrap();
+        expect_that!(&window.requested_present_mode(), eq(PresentMode::Immediate));
+        expect_that!(window.canvas_size(), eq(CanvasSize::new(150, 30)));
+        let frame = window.current_frame(&instance).unwrap();
+        expect_that!(frame.swap_chain().is_some());
+    }
+
+    #[test]
+    fn current_frame_after_resizing() {
+        let (mut window, instance) = create_window(
+            window::PhysicalSize {
+                width: 150,
+                height: 30,
+            },
+            &CanvasWindowDescriptor::default(),
+        );
+        expect_that!(&window.generation(), eq(0));
+        // Resize the window without rebuilding, leaving the swap chain stale.
+        window.window.set_inner_size(window::PhysicalSize::<u32> {
+            width: 200,
+            height: 100,
+        });
+        expect_that!(window.canvas_size(), eq(CanvasSize::new(150, 30)));
+        {
+            let frame = window.current_frame(&instance).unwrap();
+            expect_that!(frame.swap_chain().is_some());
+        }
+        expect_that!(&window.generation(), eq(1));
         expect_that!(window.canvas_size(), eq(CanvasSize::new(200, 100)));
     }
 
     #[test]
     fn default_buffer_parameters() {
-        let (mut window, _) = create_window(
+        let (mut window, instance) = create_window(
             window::PhysicalSize {
                 width: 20,
                 height: 30,
@@ -451,7 +640,7 @@ mod tests {
         );
         expect_that!(&window.depth_stencil_buffer_format(), eq(None));
 
-        let frame = window.current_frame().unwrap();
+        let frame = window.current_frame(&instance).unwrap();
         expect_that!(frame.swap_chain().is_some());
         expect_that!(frame.color_buffers().is_empty());
         expect_that!(frame.depth_stencil_buffer().is_none());
@@ -466,7 +655,7 @@ mod tests {
 
     #[test]
     fn multisampled_window() {
-        let (mut window, _) = create_window(
+        let (mut window, instance) = create_window(
             window::PhysicalSize {
                 width: 20,
                 height: 30,
@@ -484,7 +673,7 @@ mod tests {
         );
         expect_that!(&window.depth_stencil_buffer_format(), eq(None));
 
-        let frame = window.current_frame().unwrap();
+        let frame = window.current_frame(&instance).unwrap();
         expect_that!(frame.swap_chain().is_some());
         expect_that!(frame.color_buffers().is_empty());
         expect_that!(frame.depth_stencil_buffer().is_none());
@@ -499,7 +688,7 @@ mod tests {
 
     #[test]
     fn with_depth_stencil_buffer() {
-        let (mut window, _) = create_window(
+        let (mut window, instance) = create_window(
             window::PhysicalSize {
                 width: 20,
                 height: 30,
@@ -520,7 +709,7 @@ mod tests {
             eq(Some(CanvasDepthStencilBufferFormat::Depth32Float))
         );
 
-        let frame = window.current_frame().unwrap();
+        let frame = window.current_frame(&instance).unwrap();
         expect_that!(frame.swap_chain().is_some());
         expect_that!(frame.color_buffers().is_empty());
         expect_that!(frame.depth_stencil_buffer().is_some());
@@ -542,7 +731,7 @@ mod tests {
 
     #[test]
     fn multisampled_with_depth_stencil_buffer() {
-        let (mut window, _) = create_window(
+        let (mut window, instance) = create_window(
             window::PhysicalSize {
                 width: 20,
                 height: 30,
@@ -564,7 +753,7 @@ mod tests {
             eq(Some(CanvasDepthStencilBufferFormat::Depth32Float))
         );
 
-        let frame = window.current_frame().unwrap();
+        let frame = window.current_frame(&instance).unwrap();
         expect_that!(frame.swap_chain().is_some());
         expect_that!(frame.color_buffers().is_empty());
         expect_that!(frame.depth_stencil_buffer().is_some());
@@ -586,7 +775,7 @@ mod tests {
 
     #[test]
     fn non_default_color_buffer_format() {
-        let (mut window, _) = create_window(
+        let (mut window, instance) = create_window(
             window::PhysicalSize {
                 width: 20,
                 height: 30,
@@ -604,7 +793,7 @@ mod tests {
         );
         expect_that!(&window.depth_stencil_buffer_format(), eq(None));
 
-        let frame = window.current_frame().unwrap();
+        let frame = window.current_frame(&instance).unwrap();
         expect_that!(frame.swap_chain().is_some());
         expect_that!(frame.color_buffers().is_empty());
         expect_that!(frame.depth_stencil_buffer().is_none());
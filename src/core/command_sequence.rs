@@ -1,10 +1,14 @@
 use std::{default::Default, iter};
 
 use super::{
-    CanvasColorBufferFormat, CanvasDepthStencilBufferFormat, CanvasFrame, ColorOperations,
-    CommandEncoder, CommandEncoderDescriptor, DepthOperations, Instance, Operations, RenderPass,
-    RenderPassColorAttachmentDescriptor, RenderPassDepthStencilAttachmentDescriptor,
-    RenderPassDescriptor, SampleCount, StencilOperations,
+    Buffer, BufferAddress, BufferCopyView, BufferUsage, CanvasColorBufferFormat,
+    CanvasColorBufferUsage, CanvasDepthStencilBufferFormat, CanvasFrame, CanvasTexture,
+    ColorOperations, CommandEncoder, CommandEncoderDescriptor, ComputePass, DepthOperations,
+    Extent3d, FilterMode, GpuProfiler, Instance, LoadOp, Operations, Origin3d, PassTiming,
+    ProfilingError, RenderPass, RenderPassColorAttachmentDescriptor,
+    RenderPassDepthStencilAttachmentDescriptor, RenderPassDescriptor, SampleCount,
+    StencilOperations, Texture, TextureCopyView, TextureDataLayout, TextureFormat,
+    TextureFormatBlock, TextureUsage,
 };
 
 #[derive(Debug, PartialEq, Clone)]
@@ -31,15 +35,206 @@ impl Default for RenderPassOperations {
     }
 }
 
+// Color attachment indices include the swap chain, if present.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RenderPassError {
+    IncompatibleSwapChain {
+        expected_format: CanvasColorBufferFormat,
+        expected_sample_count: SampleCount,
+        actual_format: CanvasColorBufferFormat,
+        actual_sample_count: SampleCount,
+    },
+    MissingColorBuffer {
+        index: usize,
+    },
+    IncompatibleColorBuffer {
+        index: usize,
+        expected_format: CanvasColorBufferFormat,
+        expected_sample_count: SampleCount,
+        actual_format: CanvasColorBufferFormat,
+        actual_sample_count: SampleCount,
+    },
+    MissingDepthStencilBuffer,
+    IncompatibleDepthStencilBuffer {
+        expected_format: CanvasDepthStencilBufferFormat,
+        expected_sample_count: SampleCount,
+        actual_format: CanvasDepthStencilBufferFormat,
+        actual_sample_count: SampleCount,
+    },
+}
+
+impl std::fmt::Display for RenderPassError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RenderPassError::IncompatibleSwapChain {
+                expected_format,
+                expected_sample_count,
+                actual_format,
+                actual_sample_count,
+            } => write!(
+                f,
+                "Incompatible swap chain (expected {:?} with {} samples, found {:?} with {} samples)",
+                expected_format, expected_sample_count, actual_format, actual_sample_count
+            ),
+            RenderPassError::MissingColorBuffer { index } => {
+                write!(f, "Not enough color buffers (missing color buffer {})", index)
+            }
+            RenderPassError::IncompatibleColorBuffer {
+                index,
+                expected_format,
+                expected_sample_count,
+                actual_format,
+                actual_sample_count,
+            } => write!(
+                f,
+                "Incompatible color buffer {} (expected {:?} with {} samples, found {:?} with {} samples)",
+                index, expected_format, expected_sample_count, actual_format, actual_sample_count
+            ),
+            RenderPassError::MissingDepthStencilBuffer => {
+                write!(f, "Unavailable depth stencil buffer")
+            }
+            RenderPassError::IncompatibleDepthStencilBuffer {
+                expected_format,
+                expected_sample_count,
+                actual_format,
+                actual_sample_count,
+            } => write!(
+                f,
+                "Incompatible depth stencil buffer (expected {:?} with {} samples, found {:?} with {} samples)",
+                expected_format, expected_sample_count, actual_format, actual_sample_count
+            ),
+        }
+    }
+}
+
+impl std::error::Error for RenderPassError {}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CopyError {
+    MissingBufferUsage(BufferUsage),
+    MissingTextureUsage(TextureUsage),
+    MissingColorBufferUsage(CanvasColorBufferUsage),
+    IncompatibleFormats {
+        source: TextureFormat,
+        destination: TextureFormat,
+    },
+    SourceOutOfBounds,
+    DestinationOutOfBounds,
+    UnalignedCopy,
+    InvalidDataLayout,
+    MissingColorBuffer {
+        index: usize,
+    },
+    MissingBlitTarget,
+    UnfilterableFormat(TextureFormat),
+}
+
+impl std::fmt::Display for CopyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CopyError::MissingBufferUsage(usage) => {
+                write!(f, "Missing buffer usage ({:?})", usage)
+            }
+            CopyError::MissingTextureUsage(usage) => {
+                write!(f, "Missing texture usage ({:?})", usage)
+            }
+            CopyError::MissingColorBufferUsage(usage) => {
+                write!(f, "Missing color buffer usage ({:?})", usage)
+            }
+            CopyError::IncompatibleFormats {
+                source,
+                destination,
+            } => write!(
+                f,
+                "Incompatible texture formats (source {:?}, destination {:?})",
+                source, destination
+            ),
+            CopyError::SourceOutOfBounds => write!(f, "The copy source is out of bounds"),
+            CopyError::DestinationOutOfBounds => {
+                write!(f, "The copy destination is out of bounds")
+            }
+            CopyError::UnalignedCopy => write!(f, "The copy isn't properly aligned"),
+            CopyError::InvalidDataLayout => write!(f, "Invalid texture data layout"),
+            CopyError::MissingColorBuffer { index } => {
+                write!(f, "Missing source color buffer {}", index)
+            }
+            CopyError::MissingBlitTarget => write!(f, "The frame has no color attachment"),
+            CopyError::UnfilterableFormat(format) => {
+                write!(
+                    f,
+                    "Linear filtering isn't supported by {:?} textures",
+                    format
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for CopyError {}
+
 #[derive(Debug)]
 pub struct CommandSequence {
     encoder: CommandEncoder,
+    profiler: Option<GpuProfiler>,
 }
 
 impl CommandSequence {
     pub fn new(instance: &Instance) -> Self {
-        let encoder = CommandEncoder::new(&instance, &CommandEncoderDescriptor::default());
-        Self { encoder }
+        Self::new_with_label(instance, None)
+    }
+
+    pub fn new_with_label(instance: &Instance, label: Option<&str>) -> Self {
+        let encoder = CommandEncoder::new(&instance, &CommandEncoderDescriptor { label });
+        Self {
+            encoder,
+            profiler: None,
+        }
+    }
+
+    // Pass timings are only measured if the instance supports timestamp
+    // queries. Otherwise profiling has no effect on the recorded commands.
+    pub fn enable_profiling(&mut self, instance: &Instance) {
+        if self.profiler.is_none() {
+            self.profiler = Some(GpuProfiler::new(instance));
+        }
+    }
+
+    pub fn is_profiling_enabled(&self) -> bool {
+        self.profiler.is_some()
+    }
+
+    pub fn is_measuring_pass_timings(&self) -> bool {
+        match &self.profiler {
+            Some(profiler) => profiler.timestamps_supported(),
+            None => false,
+        }
+    }
+
+    pub fn profiled_pass_names(&self) -> &[String] {
+        match &self.profiler {
+            Some(profiler) => profiler.pass_names(),
+            None => &[],
+        }
+    }
+
+    // Name reported for the next render or compute pass, instead of the
+    // default one based on the pass index.
+    pub fn set_next_pass_name<S: Into<String>>(&mut self, name: S) {
+        if let Some(profiler) = &mut self.profiler {
+            profiler.name_next_pass(name.into());
+        }
+    }
+
+    pub fn push_debug_group(&mut self, label: &str) {
+        self.encoder.push_debug_group(label);
+    }
+
+    pub fn pop_debug_group(&mut self) {
+        self.encoder.pop_debug_group();
+    }
+
+    pub fn insert_debug_marker(&mut self, label: &str) {
+        self.encoder.insert_debug_marker(label);
     }
 
     pub fn begin_render_pass<'a>(
@@ -47,7 +242,7 @@ impl CommandSequence {
         canvas_frame: &'a CanvasFrame,
         requirements: &RenderPassRequirements,
         operations: &RenderPassOperations,
-    ) -> RenderPass<'a> {
+    ) -> Result<RenderPass<'a>, RenderPassError> {
         // Define color attachments.
         let has_swap_chain = canvas_frame.swap_chain().is_some();
         let required_color_buffer_count = requirements.color_buffer_formats.len();
@@ -60,11 +255,16 @@ impl CommandSequence {
             };
             if i == 0 && has_swap_chain {
                 let swap_chain = canvas_frame.swap_chain().unwrap();
-                assert!(
-                    required_format == swap_chain.format()
-                        && requirements.sample_count == swap_chain.sample_count(),
-                    "Incompatible swap chain"
-                );
+                if required_format != swap_chain.format()
+                    || requirements.sample_count != swap_chain.sample_count()
+                {
+                    return Err(RenderPassError::IncompatibleSwapChain {
+                        expected_format: required_format,
+                        expected_sample_count: requirements.sample_count,
+                        actual_format: swap_chain.format(),
+                        actual_sample_count: swap_chain.sample_count(),
+                    });
+                }
                 color_attachments.push(RenderPassColorAttachmentDescriptor {
                     attachment: swap_chain.attachment(),
                     resolve_target: swap_chain.resolve_target(),
@@ -72,15 +272,21 @@ impl CommandSequence {
                 });
             } else {
                 let buffer_index = i - if has_swap_chain { 1 } else { 0 };
-                let color_buffer = canvas_frame
-                    .color_buffers()
-                    .get(buffer_index)
-                    .expect("Not enough color buffers");
-                assert!(
-                    required_format == color_buffer.format()
-                        && requirements.sample_count == color_buffer.sample_count(),
-                    "Incompatible color buffer"
-                );
+                let color_buffer = match canvas_frame.color_buffers().get(buffer_index) {
+                    Some(v) => v,
+                    None => return Err(RenderPassError::MissingColorBuffer { index: i }),
+                };
+                if required_format != color_buffer.format()
+                    || requirements.sample_count != color_buffer.sample_count()
+                {
+                    return Err(RenderPassError::IncompatibleColorBuffer {
+                        index: i,
+                        expected_format: required_format,
+                        expected_sample_count: requirements.sample_count,
+                        actual_format: color_buffer.format(),
+                        actual_sample_count: color_buffer.sample_count(),
+                    });
+                }
                 color_attachments.push(RenderPassColorAttachmentDescriptor {
                     attachment: color_buffer.attachment(),
                     resolve_target: color_buffer.resolve_target(),
@@ -93,44 +299,386 @@ impl CommandSequence {
         let depth_stencil_attachment = match requirements.depth_stencil_buffer_format {
             Some(required_format) => match canvas_frame.depth_stencil_buffer() {
                 Some(ds_buffer) => {
-                    assert!(
-                        required_format == ds_buffer.format()
-                            && requirements.sample_count == ds_buffer.sample_count(),
-                        "Incompatible depth stencil buffer"
-                    );
+                    if required_format != ds_buffer.format()
+                        || requirements.sample_count != ds_buffer.sample_count()
+                    {
+                        return Err(RenderPassError::IncompatibleDepthStencilBuffer {
+                            expected_format: required_format,
+                            expected_sample_count: requirements.sample_count,
+                            actual_format: ds_buffer.format(),
+                            actual_sample_count: ds_buffer.sample_count(),
+                        });
+                    }
                     Some(RenderPassDepthStencilAttachmentDescriptor {
                         attachment: ds_buffer.attachment(),
                         depth_ops: operations.depth_operations,
                         stencil_ops: operations.stencil_operations,
                     })
                 }
-                None => panic!("Unavailable depth stencil buffer"),
+                None => return Err(RenderPassError::MissingDepthStencilBuffer),
             },
             None => None,
         };
 
         // Begin the render pass.
+        if let Some(profiler) = &mut self.profiler {
+            profiler.begin_pass("render");
+        }
         let render_pass_desc = RenderPassDescriptor {
             color_attachments: color_attachments.as_slice(),
             depth_stencil_attachment,
         };
-        self.encoder.begin_render_pass(&render_pass_desc)
+        Ok(self.encoder.begin_render_pass(&render_pass_desc))
+    }
+
+    pub fn begin_compute_pass(&mut self) -> ComputePass {
+        if let Some(profiler) = &mut self.profiler {
+            profiler.begin_pass("compute");
+        }
+        ComputePass::new(&mut self.encoder)
+    }
+
+    pub fn copy_buffer_to_buffer(
+        &mut self,
+        source: &Buffer,
+        source_offset: BufferAddress,
+        destination: &Buffer,
+        destination_offset: BufferAddress,
+        size: BufferAddress,
+    ) -> Result<(), CopyError> {
+        check_buffer_usage(source, BufferUsage::COPY_SRC)?;
+        check_buffer_usage(destination, BufferUsage::COPY_DST)?;
+        let align = wgpu::COPY_BUFFER_ALIGNMENT;
+        if source_offset % align != 0 || destination_offset % align != 0 || size % align != 0 {
+            return Err(CopyError::UnalignedCopy);
+        }
+        if source_offset + size > source.size() {
+            return Err(CopyError::SourceOutOfBounds);
+        }
+        if destination_offset + size > destination.size() {
+            return Err(CopyError::DestinationOutOfBounds);
+        }
+        self.encoder.copy_buffer_to_buffer(
+            source,
+            source_offset,
+            destination,
+            destination_offset,
+            size,
+        );
+        Ok(())
+    }
+
+    // As for any buffer copy, the bytes per row must be a multiple of
+    // wgpu::COPY_BYTES_PER_ROW_ALIGNMENT.
+    pub fn copy_buffer_to_texture(
+        &mut self,
+        source: &Buffer,
+        layout: TextureDataLayout,
+        destination: &Texture,
+        mip_level: u32,
+        origin: Origin3d,
+        extent: Extent3d,
+    ) -> Result<(), CopyError> {
+        check_buffer_usage(source, BufferUsage::COPY_SRC)?;
+        check_texture_usage(destination, TextureUsage::COPY_DST)?;
+        check_texture_region(
+            destination,
+            mip_level,
+            origin,
+            extent,
+            CopyError::DestinationOutOfBounds,
+        )?;
+        let block = TextureFormatBlock::from(destination.format());
+        let bytes_per_row = (extent.width / block.width * block.byte_count) as BufferAddress;
+        let row_count = (extent.height / block.height) as BufferAddress;
+        let rows_per_image = (layout.rows_per_image / block.height) as BufferAddress;
+        if layout.bytes_per_row % wgpu::COPY_BYTES_PER_ROW_ALIGNMENT != 0
+            || layout.offset % block.byte_count as BufferAddress != 0
+        {
+            return Err(CopyError::UnalignedCopy);
+        }
+        if (layout.bytes_per_row as BufferAddress) < bytes_per_row
+            || (extent.depth > 1 && rows_per_image < row_count)
+        {
+            return Err(CopyError::InvalidDataLayout);
+        }
+        if row_count > 0 && extent.depth > 0 {
+            let required_size = layout.offset
+                + layout.bytes_per_row as BufferAddress
+                    * (rows_per_image * (extent.depth - 1) as BufferAddress + row_count - 1)
+                + bytes_per_row;
+            if required_size > source.size() {
+                return Err(CopyError::SourceOutOfBounds);
+            }
+        }
+        self.encoder.copy_buffer_to_texture(
+            BufferCopyView {
+                buffer: source,
+                layout,
+            },
+            TextureCopyView {
+                texture: destination,
+                mip_level,
+                origin,
+            },
+            extent,
+        );
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn copy_texture_to_texture(
+        &mut self,
+        source: &Texture,
+        source_mip_level: u32,
+        source_origin: Origin3d,
+        destination: &Texture,
+        destination_mip_level: u32,
+        destination_origin: Origin3d,
+        extent: Extent3d,
+    ) -> Result<(), CopyError> {
+        check_texture_usage(source, TextureUsage::COPY_SRC)?;
+        check_texture_usage(destination, TextureUsage::COPY_DST)?;
+        if source.format() != destination.format() {
+            return Err(CopyError::IncompatibleFormats {
+                source: source.format(),
+                destination: destination.format(),
+            });
+        }
+        check_texture_region(
+            source,
+            source_mip_level,
+            source_origin,
+            extent,
+            CopyError::SourceOutOfBounds,
+        )?;
+        check_texture_region(
+            destination,
+            destination_mip_level,
+            destination_origin,
+            extent,
+            CopyError::DestinationOutOfBounds,
+        )?;
+        self.encoder.copy_texture_to_texture(
+            TextureCopyView {
+                texture: source,
+                mip_level: source_mip_level,
+                origin: source_origin,
+            },
+            TextureCopyView {
+                texture: destination,
+                mip_level: destination_mip_level,
+                origin: destination_origin,
+            },
+            extent,
+        );
+        Ok(())
+    }
+
+    // The source color buffer is scaled to cover the first color attachment
+    // of the frame (the swap chain, if present).
+    pub fn blit(
+        &mut self,
+        instance: &Instance,
+        source: &CanvasTexture,
+        color_buffer_index: usize,
+        target: &CanvasFrame,
+        filter: FilterMode,
+    ) -> Result<(), CopyError> {
+        let source_texture = match source.color_texture(color_buffer_index) {
+            Some(v) => v,
+            None => {
+                return Err(CopyError::MissingColorBuffer {
+                    index: color_buffer_index,
+                })
+            }
+        };
+        if !source_texture.usage().contains(TextureUsage::SAMPLED) {
+            return Err(CopyError::MissingColorBufferUsage(
+                CanvasColorBufferUsage::SAMPLED,
+            ));
+        }
+        if filter == FilterMode::Linear && !is_filterable(source_texture.format()) {
+            return Err(CopyError::UnfilterableFormat(source_texture.format()));
+        }
+        let source_view = source.color_texture_view(color_buffer_index).unwrap();
+
+        let (attachment, resolve_target, format, sample_count) = match target.swap_chain() {
+            Some(swap_chain) => (
+                swap_chain.attachment(),
+                swap_chain.resolve_target(),
+                swap_chain.format(),
+                swap_chain.sample_count(),
+            ),
+            None => match target.color_buffers().first() {
+                Some(color_buffer) => (
+                    color_buffer.attachment(),
+                    color_buffer.resolve_target(),
+                    color_buffer.format(),
+                    color_buffer.sample_count(),
+                ),
+                None => return Err(CopyError::MissingBlitTarget),
+            },
+        };
+
+        if let Some(profiler) = &mut self.profiler {
+            profiler.begin_pass("blit");
+        }
+        let blit_pipeline =
+            instance.blit_pipeline(TextureFormat::from(format), sample_count, filter);
+        let bind_group = blit_pipeline.bind_group(instance, source_view);
+        let mut pass = self.encoder.begin_render_pass(&RenderPassDescriptor {
+            color_attachments: &[RenderPassColorAttachmentDescriptor {
+                attachment,
+                resolve_target,
+                ops: Operations {
+                    load: LoadOp::Load,
+                    store: true,
+                },
+            }],
+            depth_stencil_attachment: None,
+        });
+        blit_pipeline.draw(&mut pass, &bind_group);
+        Ok(())
     }
 
     pub fn submit(self, instance: &Instance) {
         instance.submit(iter::once(self.encoder.finish()))
     }
+
+    // Submits the commands and reports the GPU duration of each pass.
+    pub fn submit_profiled(self, instance: &Instance) -> Result<Vec<PassTiming>, ProfilingError> {
+        instance.submit(iter::once(self.encoder.finish()));
+        match self.profiler {
+            Some(profiler) => profiler.resolve(),
+            None => Err(ProfilingError::NotEnabled),
+        }
+    }
+}
+
+fn check_buffer_usage(buffer: &Buffer, usage: BufferUsage) -> Result<(), CopyError> {
+    if buffer.usage().contains(usage) {
+        Ok(())
+    } else {
+        Err(CopyError::MissingBufferUsage(usage))
+    }
+}
+
+fn check_texture_usage(texture: &Texture, usage: TextureUsage) -> Result<(), CopyError> {
+    if texture.usage().contains(usage) {
+        Ok(())
+    } else {
+        Err(CopyError::MissingTextureUsage(usage))
+    }
+}
+
+// 32 bit float textures can't be sampled with linear filtering.
+fn is_filterable(format: TextureFormat) -> bool {
+    !matches!(
+        format,
+        TextureFormat::R32Float | TextureFormat::Rg32Float | TextureFormat::Rgba32Float
+    )
+}
+
+fn check_texture_region(
+    texture: &Texture,
+    mip_level: u32,
+    origin: Origin3d,
+    extent: Extent3d,
+    out_of_bounds_error: CopyError,
+) -> Result<(), CopyError> {
+    if mip_level >= texture.mip_level_count() {
+        return Err(out_of_bounds_error);
+    }
+    let size = texture.mip_level_size(mip_level);
+    if origin.x + extent.width > size.width
+        || origin.y + extent.height > size.height
+        || origin.z + extent.depth > size.depth
+    {
+        return Err(out_of_bounds_error);
+    }
+    let block = TextureFormatBlock::from(texture.format());
+    if origin.x % block.width != 0
+        || origin.y % block.height != 0
+        || extent.width % block.width != 0
+        || extent.height % block.height != 0
+    {
+        return Err(CopyError::UnalignedCopy);
+    }
+    Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    use galvanic_assert::{matchers::*, *};
+
     use crate::core::{
-        CanvasBuffer, CanvasBufferColorBufferDescriptor, CanvasBufferDescriptor,
-        CanvasColorBufferUsage, CanvasSize, InstanceDescriptor,
+        include_spirv, BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout,
+        BindGroupLayoutDescriptor, BindGroupLayoutEntry, BindingResource, BindingType,
+        BufferDescriptor, BufferInitDescriptor, Canvas, CanvasBuffer,
+        CanvasBufferColorBufferDescriptor, CanvasBufferDepthStencilBufferDescriptor,
+        CanvasBufferDescriptor, CanvasColorBufferUsage, CanvasDepthStencilBufferUsage, CanvasSize,
+        CanvasTextureColorBufferDescriptor, CanvasTextureDescriptor, ColorF64, ComputePipeline,
+        ComputePipelineDescriptor, InstanceDescriptor, Maintain, MapMode, PipelineLayout,
+        PipelineLayoutDescriptor, ProgrammableStageDescriptor, ShaderModule, ShaderStage,
+        TextureDescriptor, TextureDimension,
     };
 
+    fn read_buffer(instance: &Instance, buffer: &Buffer) -> Vec<u8> {
+        let slice = buffer.slice(..);
+        let future = slice.map_async(MapMode::Read);
+        instance.poll(Maintain::Wait);
+        futures::executor::block_on(future).unwrap();
+        let bytes = slice.get_mapped_range().to_vec();
+        buffer.unmap();
+        bytes
+    }
+
+    fn create_texture(
+        instance: &Instance,
+        width: u32,
+        height: u32,
+        usage: TextureUsage,
+    ) -> Texture {
+        Texture::new(
+            instance,
+            &TextureDescriptor {
+                label: None,
+                size: Extent3d {
+                    width,
+                    height,
+                    depth: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba8Unorm,
+                usage,
+            },
+        )
+    }
+
+    fn create_canvas(
+        instance: &Instance,
+        size: CanvasSize,
+        usage: CanvasColorBufferUsage,
+    ) -> CanvasTexture {
+        CanvasTexture::new(
+            instance,
+            &CanvasTextureDescriptor {
+                size,
+                color_buffer_descriptors: vec![CanvasTextureColorBufferDescriptor {
+                    format: CanvasColorBufferFormat::Rgba8Unorm,
+                    usage,
+                }],
+                ..CanvasTextureDescriptor::default()
+            },
+        )
+        .unwrap()
+    }
+
     #[test]
     fn creation() {
         let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
@@ -144,6 +692,7 @@ mod tests {
         let mut buffer = CanvasBuffer::new(
             &instance,
             &CanvasBufferDescriptor {
+                label: None,
                 size: CanvasSize::new(12, 20),
                 sample_count: 2,
                 swap_chain_descriptor: None,
@@ -151,47 +700,229 @@ mod tests {
                     format: CanvasColorBufferFormat::default(),
                     usage: CanvasColorBufferUsage::empty(),
                 }],
-                depth_stencil_buffer_format: Some(CanvasDepthStencilBufferFormat::Depth32Float),
+                depth_stencil_buffer_descriptor: Some(CanvasBufferDepthStencilBufferDescriptor {
+                    format: CanvasDepthStencilBufferFormat::Depth32Float,
+                    usage: CanvasDepthStencilBufferUsage::empty(),
+                }),
             },
-        );
+        )
+        .unwrap();
 
         {
             let frame = buffer.current_frame().unwrap();
-            let _rpass = cmd_seq.begin_render_pass(
-                &frame,
-                &RenderPassRequirements {
-                    sample_count: 2,
-                    color_buffer_formats: vec![CanvasColorBufferFormat::default()],
-                    depth_stencil_buffer_format: Some(CanvasDepthStencilBufferFormat::Depth32Float),
-                },
-                &RenderPassOperations::default(),
-            );
+            let _rpass = cmd_seq
+                .begin_render_pass(
+                    &frame,
+                    &RenderPassRequirements {
+                        sample_count: 2,
+                        color_buffer_formats: vec![CanvasColorBufferFormat::default()],
+                        depth_stencil_buffer_format: Some(
+                            CanvasDepthStencilBufferFormat::Depth32Float,
+                        ),
+                    },
+                    &RenderPassOperations::default(),
+                )
+                .unwrap();
         }
 
         {
             let frame = buffer.current_frame().unwrap();
-            let _rpass = cmd_seq.begin_render_pass(
-                &frame,
-                &RenderPassRequirements {
-                    sample_count: 2,
-                    color_buffer_formats: vec![CanvasColorBufferFormat::default()],
-                    depth_stencil_buffer_format: Some(CanvasDepthStencilBufferFormat::Depth32Float),
+            let _rpass = cmd_seq
+                .begin_render_pass(
+                    &frame,
+                    &RenderPassRequirements {
+                        sample_count: 2,
+                        color_buffer_formats: vec![CanvasColorBufferFormat::default()],
+                        depth_stencil_buffer_format: Some(
+                            CanvasDepthStencilBufferFormat::Depth32Float,
+                        ),
+                    },
+                    &RenderPassOperations::default(),
+                )
+                .unwrap();
+        }
+
+        cmd_seq.submit(&instance);
+    }
+
+    #[test]
+    fn compute_pass() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let mut cmd_seq = CommandSequence::new(&instance);
+        {
+            let _cpass = cmd_seq.begin_compute_pass();
+        }
+        {
+            let _cpass = cmd_seq.begin_compute_pass();
+        }
+        cmd_seq.submit(&instance);
+    }
+
+    #[test]
+    fn compute_pass_dispatch() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let values: Vec<u32> = (0..8).collect();
+        let storage_buffer = Buffer::init(
+            &instance,
+            &BufferInitDescriptor {
+                label: None,
+                contents: bytemuck::cast_slice(&values),
+                usage: BufferUsage::STORAGE | BufferUsage::COPY_SRC,
+            },
+        );
+        let readback_buffer = Buffer::new(
+            &instance,
+            &BufferDescriptor {
+                label: None,
+                size: storage_buffer.size(),
+                usage: BufferUsage::COPY_DST | BufferUsage::MAP_READ,
+                mapped_at_creation: false,
+            },
+        );
+        let bind_group_layout = BindGroupLayout::new(
+            &instance,
+            &BindGroupLayoutDescriptor {
+                label: None,
+                entries: &[BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility: ShaderStage::COMPUTE,
+                    ty: BindingType::StorageBuffer {
+                        dynamic: false,
+                        min_binding_size: None,
+                        readonly: false,
+                    },
+                    count: None,
+                }],
+            },
+        );
+        let bind_group = BindGroup::new(
+            &instance,
+            &BindGroupDescriptor {
+                label: None,
+                layout: &bind_group_layout,
+                entries: &[BindGroupEntry {
+                    binding: 0,
+                    resource: BindingResource::Buffer(storage_buffer.slice(..)),
+                }],
+            },
+        );
+        let pipeline_layout = PipelineLayout::new(
+            &instance,
+            &PipelineLayoutDescriptor {
+                label: None,
+                bind_group_layouts: &[&bind_group_layout],
+                push_constant_ranges: &[],
+            },
+        );
+        let module = ShaderModule::new(
+            &instance,
+            include_spirv!("shaders/gen/spirv/double_values.comp.spv"),
+        );
+        let pipeline = ComputePipeline::new(
+            &instance,
+            &ComputePipelineDescriptor {
+                label: None,
+                layout: Some(&pipeline_layout),
+                compute_stage: ProgrammableStageDescriptor {
+                    module: &module,
+                    entry_point: "main",
                 },
-                &RenderPassOperations::default(),
+            },
+        );
+
+        let mut cmd_seq = CommandSequence::new(&instance);
+        {
+            let mut cpass = cmd_seq.begin_compute_pass();
+            cpass.set_pipeline(&pipeline);
+            cpass.set_bind_group(0, &bind_group, &[]);
+            cpass.dispatch(values.len() as u32, 1, 1);
+        }
+        cmd_seq
+            .copy_buffer_to_buffer(
+                &storage_buffer,
+                0,
+                &readback_buffer,
+                0,
+                storage_buffer.size(),
+            )
+            .unwrap();
+        cmd_seq.submit(&instance);
+
+        let bytes = read_buffer(&instance, &readback_buffer);
+        let expected_values: Vec<u32> = values.iter().map(|x| x * 2).collect();
+        expect_that!(
+            &bytes.as_slice(),
+            eq(bytemuck::cast_slice::<u32, u8>(&expected_values))
+        );
+    }
+
+    #[test]
+    fn profiling() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let mut cmd_seq = CommandSequence::new(&instance);
+        expect_that!(!cmd_seq.is_profiling_enabled());
+        cmd_seq.begin_compute_pass();
+        expect_that!(cmd_seq.profiled_pass_names().is_empty());
+
+        cmd_seq.enable_profiling(&instance);
+        expect_that!(cmd_seq.is_profiling_enabled());
+        expect_that!(
+            &cmd_seq.is_measuring_pass_timings(),
+            eq(instance.supports_timestamp_queries())
+        );
+        cmd_seq.begin_compute_pass();
+        cmd_seq.set_next_pass_name("simulation");
+        cmd_seq.begin_compute_pass();
+        cmd_seq.begin_compute_pass();
+        expect_that!(
+            &cmd_seq.profiled_pass_names().to_vec(),
+            eq(vec![
+                String::from("compute pass 0"),
+                String::from("simulation"),
+                String::from("compute pass 2")
+            ])
+        );
+
+        let timings = cmd_seq.submit_profiled(&instance);
+        if !instance.supports_timestamp_queries() {
+            expect_that!(
+                &timings,
+                eq(Err(ProfilingError::TimestampQueriesNotSupported))
             );
         }
+    }
+
+    #[test]
+    fn profiling_not_enabled() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let cmd_seq = CommandSequence::new(&instance);
+        expect_that!(
+            &cmd_seq.submit_profiled(&instance),
+            eq(Err(ProfilingError::NotEnabled))
+        );
+    }
 
+    #[test]
+    fn debug_groups() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let mut cmd_seq = CommandSequence::new_with_label(&instance, Some("debug groups"));
+        cmd_seq.push_debug_group("simulation");
+        cmd_seq.insert_debug_marker("first step");
+        {
+            let _cpass = cmd_seq.begin_compute_pass();
+        }
+        cmd_seq.pop_debug_group();
         cmd_seq.submit(&instance);
     }
 
     #[test]
-    #[should_panic(expected = "Incompatible color buffer")]
     fn begin_render_pass_error_incompatible_sample_count() {
         let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
         let mut cmd_seq = CommandSequence::new(&instance);
         let mut buffer = CanvasBuffer::new(
             &instance,
             &CanvasBufferDescriptor {
+                label: None,
                 size: CanvasSize::new(12, 20),
                 sample_count: 1,
                 swap_chain_descriptor: None,
@@ -199,13 +930,17 @@ mod tests {
                     format: CanvasColorBufferFormat::default(),
                     usage: CanvasColorBufferUsage::empty(),
                 }],
-                depth_stencil_buffer_format: Some(CanvasDepthStencilBufferFormat::Depth32Float),
+                depth_stencil_buffer_descriptor: Some(CanvasBufferDepthStencilBufferDescriptor {
+                    format: CanvasDepthStencilBufferFormat::Depth32Float,
+                    usage: CanvasDepthStencilBufferUsage::empty(),
+                }),
             },
-        );
+        )
+        .unwrap();
 
         {
             let frame = buffer.current_frame().unwrap();
-            let _rpass = cmd_seq.begin_render_pass(
+            let rpass = cmd_seq.begin_render_pass(
                 &frame,
                 &RenderPassRequirements {
                     sample_count: 2,
@@ -214,27 +949,41 @@ mod tests {
                 },
                 &RenderPassOperations::default(),
             );
+            expect_that!(
+                &rpass.unwrap_err(),
+                eq(RenderPassError::IncompatibleColorBuffer {
+                    index: 0,
+                    expected_format: CanvasColorBufferFormat::default(),
+                    expected_sample_count: 2,
+                    actual_format: CanvasColorBufferFormat::default(),
+                    actual_sample_count: 1,
+                })
+            );
         }
     }
 
     #[test]
-    #[should_panic(expected = "Not enough color buffers")]
     fn begin_render_pass_error_not_enough_color_buffers() {
         let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
         let mut cmd_seq = CommandSequence::new(&instance);
         let mut buffer = CanvasBuffer::new(
             &instance,
             &CanvasBufferDescriptor {
+                label: None,
                 size: CanvasSize::new(12, 20),
                 sample_count: 2,
                 swap_chain_descriptor: None,
                 color_buffer_descriptors: Vec::new(),
-                depth_stencil_buffer_format: Some(CanvasDepthStencilBufferFormat::Depth32Float),
+                depth_stencil_buffer_descriptor: Some(CanvasBufferDepthStencilBufferDescriptor {
+                    format: CanvasDepthStencilBufferFormat::Depth32Float,
+                    usage: CanvasDepthStencilBufferUsage::empty(),
+                }),
             },
-        );
+        )
+        .unwrap();
 
         let frame = buffer.current_frame().unwrap();
-        let _rpass = cmd_seq.begin_render_pass(
+        let rpass = cmd_seq.begin_render_pass(
             &frame,
             &RenderPassRequirements {
                 sample_count: 2,
@@ -243,16 +992,20 @@ mod tests {
             },
             &RenderPassOperations::default(),
         );
+        expect_that!(
+            &rpass.unwrap_err(),
+            eq(RenderPassError::MissingColorBuffer { index: 0 })
+        );
     }
 
     #[test]
-    #[should_panic(expected = "Incompatible color buffer")]
     fn begin_render_pass_error_wrong_color_buffer_format() {
         let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
         let mut cmd_seq = CommandSequence::new(&instance);
         let mut buffer = CanvasBuffer::new(
             &instance,
             &CanvasBufferDescriptor {
+                label: None,
                 size: CanvasSize::new(12, 20),
                 sample_count: 2,
                 swap_chain_descriptor: None,
@@ -260,12 +1013,13 @@ mod tests {
                     format: CanvasColorBufferFormat::Bgra8Unorm,
                     usage: CanvasColorBufferUsage::empty(),
                 }],
-                depth_stencil_buffer_format: None,
+                depth_stencil_buffer_descriptor: None,
             },
-        );
+        )
+        .unwrap();
 
         let frame = buffer.current_frame().unwrap();
-        let _rpass = cmd_seq.begin_render_pass(
+        let rpass = cmd_seq.begin_render_pass(
             &frame,
             &RenderPassRequirements {
                 sample_count: 2,
@@ -274,16 +1028,26 @@ mod tests {
             },
             &RenderPassOperations::default(),
         );
+        expect_that!(
+            &rpass.unwrap_err(),
+            eq(RenderPassError::IncompatibleColorBuffer {
+                index: 0,
+                expected_format: CanvasColorBufferFormat::Bgra8UnormSrgb,
+                expected_sample_count: 2,
+                actual_format: CanvasColorBufferFormat::Bgra8Unorm,
+                actual_sample_count: 2,
+            })
+        );
     }
 
     #[test]
-    #[should_panic(expected = "Unavailable depth stencil buffer")]
     fn begin_render_pass_error_no_depth_stencil_buffer() {
         let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
         let mut cmd_seq = CommandSequence::new(&instance);
         let mut buffer = CanvasBuffer::new(
             &instance,
             &CanvasBufferDescriptor {
+                label: None,
                 size: CanvasSize::new(12, 20),
                 sample_count: 2,
                 swap_chain_descriptor: None,
@@ -291,12 +1055,13 @@ mod tests {
                     format: CanvasColorBufferFormat::default(),
                     usage: CanvasColorBufferUsage::empty(),
                 }],
-                depth_stencil_buffer_format: None,
+                depth_stencil_buffer_descriptor: None,
             },
-        );
+        )
+        .unwrap();
 
         let frame = buffer.current_frame().unwrap();
-        let _rpass = cmd_seq.begin_render_pass(
+        let rpass = cmd_seq.begin_render_pass(
             &frame,
             &RenderPassRequirements {
                 sample_count: 2,
@@ -305,26 +1070,34 @@ mod tests {
             },
             &RenderPassOperations::default(),
         );
+        expect_that!(
+            &rpass.unwrap_err(),
+            eq(RenderPassError::MissingDepthStencilBuffer)
+        );
     }
 
     #[test]
-    #[should_panic(expected = "Incompatible depth stencil buffer")]
     fn begin_render_pass_error_wrong_depth_stencil_buffer_format() {
         let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
         let mut cmd_seq = CommandSequence::new(&instance);
         let mut buffer = CanvasBuffer::new(
             &instance,
             &CanvasBufferDescriptor {
+                label: None,
                 size: CanvasSize::new(12, 20),
                 sample_count: 2,
                 swap_chain_descriptor: None,
                 color_buffer_descriptors: Vec::new(),
-                depth_stencil_buffer_format: Some(CanvasDepthStencilBufferFormat::Depth24Plus),
+                depth_stencil_buffer_descriptor: Some(CanvasBufferDepthStencilBufferDescriptor {
+                    format: CanvasDepthStencilBufferFormat::Depth24Plus,
+                    usage: CanvasDepthStencilBufferUsage::empty(),
+                }),
             },
-        );
+        )
+        .unwrap();
 
         let frame = buffer.current_frame().unwrap();
-        let _rpass = cmd_seq.begin_render_pass(
+        let rpass = cmd_seq.begin_render_pass(
             &frame,
             &RenderPassRequirements {
                 sample_count: 2,
@@ -333,5 +1106,381 @@ mod tests {
             },
             &RenderPassOperations::default(),
         );
+        expect_that!(
+            &rpass.unwrap_err(),
+            eq(RenderPassError::IncompatibleDepthStencilBuffer {
+                expected_format: CanvasDepthStencilBufferFormat::Depth32Float,
+                expected_sample_count: 2,
+                actual_format: CanvasDepthStencilBufferFormat::Depth24Plus,
+                actual_sample_count: 2,
+            })
+        );
+    }
+
+    #[test]
+    fn copy_buffer_to_buffer() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let source_data: Vec<u8> = (0..16).collect();
+        let source = Buffer::init(
+            &instance,
+            &BufferInitDescriptor {
+                label: None,
+                contents: &source_data,
+                usage: BufferUsage::COPY_SRC,
+            },
+        );
+        let destination = Buffer::new(
+            &instance,
+            &BufferDescriptor {
+                label: None,
+                size: 8,
+                usage: BufferUsage::COPY_DST | BufferUsage::MAP_READ,
+                mapped_at_creation: false,
+            },
+        );
+        expect_that!(&source.size(), eq(16));
+
+        let mut cmd_seq = CommandSequence::new(&instance);
+        cmd_seq
+            .copy_buffer_to_buffer(&source, 4, &destination, 0, 8)
+            .unwrap();
+        expect_that!(
+            &cmd_seq.copy_buffer_to_buffer(&source, 12, &destination, 0, 8),
+            eq(Err(CopyError::SourceOutOfBounds))
+        );
+        expect_that!(
+            &cmd_seq.copy_buffer_to_buffer(&source, 0, &destination, 4, 8),
+            eq(Err(CopyError::DestinationOutOfBounds))
+        );
+        expect_that!(
+            &cmd_seq.copy_buffer_to_buffer(&source, 2, &destination, 0, 4),
+            eq(Err(CopyError::UnalignedCopy))
+        );
+        expect_that!(
+            &cmd_seq.copy_buffer_to_buffer(&destination, 0, &source, 0, 4),
+            eq(Err(CopyError::MissingBufferUsage(BufferUsage::COPY_SRC)))
+        );
+        cmd_seq.submit(&instance);
+
+        expect_that!(
+            &read_buffer(&instance, &destination),
+            eq((4..12).collect::<Vec<u8>>())
+        );
+    }
+
+    #[test]
+    fn copy_buffer_to_texture() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let mut source_data = vec![0; 512];
+        for i in 0..8 {
+            source_data[i] = i as u8;
+            source_data[256 + i] = 8 + i as u8;
+        }
+        let source = Buffer::init(
+            &instance,
+            &BufferInitDescriptor {
+                label: None,
+                contents: &source_data,
+                usage: BufferUsage::COPY_SRC,
+            },
+        );
+        let texture = create_texture(
+            &instance,
+            2,
+            2,
+            TextureUsage::COPY_DST | TextureUsage::COPY_SRC,
+        );
+        let layout = TextureDataLayout {
+            offset: 0,
+            bytes_per_row: 256,
+            rows_per_image: 2,
+        };
+
+        let mut cmd_seq = CommandSequence::new(&instance);
+        cmd_seq
+            .copy_buffer_to_texture(
+                &source,
+                layout.clone(),
+                &texture,
+                0,
+                Origin3d::ZERO,
+                *texture.size(),
+            )
+            .unwrap();
+        expect_that!(
+            &cmd_seq.copy_buffer_to_texture(
+                &source,
+                TextureDataLayout {
+                    bytes_per_row: 8,
+                    ..layout.clone()
+                },
+                &texture,
+                0,
+                Origin3d::ZERO,
+                *texture.size(),
+            ),
+            eq(Err(CopyError::UnalignedCopy))
+        );
+        expect_that!(
+            &cmd_seq.copy_buffer_to_texture(
+                &source,
+                TextureDataLayout {
+                    offset: 256,
+                    ..layout.clone()
+                },
+                &texture,
+                0,
+                Origin3d::ZERO,
+                *texture.size(),
+            ),
+            eq(Err(CopyError::SourceOutOfBounds))
+        );
+        expect_that!(
+            &cmd_seq.copy_buffer_to_texture(
+                &source,
+                layout,
+                &texture,
+                0,
+                Origin3d { x: 1, y: 0, z: 0 },
+                *texture.size(),
+            ),
+            eq(Err(CopyError::DestinationOutOfBounds))
+        );
+        cmd_seq.submit(&instance);
+
+        expect_that!(
+            &texture.read_bytes(&instance, 0, Origin3d::ZERO, *texture.size()),
+            eq((0..16).collect::<Vec<u8>>())
+        );
+    }
+
+    #[test]
+    fn copy_texture_to_texture() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let source = create_texture(
+            &instance,
+            2,
+            2,
+            TextureUsage::COPY_SRC | TextureUsage::COPY_DST,
+        );
+        source.write(
+            &instance,
+            0,
+            Origin3d::ZERO,
+            &(0..16).collect::<Vec<u8>>(),
+            TextureDataLayout {
+                offset: 0,
+                bytes_per_row: 8,
+                rows_per_image: 2,
+            },
+            *source.size(),
+        );
+        let destination = create_texture(
+            &instance,
+            3,
+            1,
+            TextureUsage::COPY_DST | TextureUsage::COPY_SRC,
+        );
+        let extent = Extent3d {
+            width: 1,
+            height: 1,
+            depth: 1,
+        };
+
+        let mut cmd_seq = CommandSequence::new(&instance);
+        cmd_seq
+            .copy_texture_to_texture(
+                &source,
+                0,
+                Origin3d { x: 1, y: 1, z: 0 },
+                &destination,
+                0,
+                Origin3d { x: 2, y: 0, z: 0 },
+                extent,
+            )
+            .unwrap();
+        expect_that!(
+            &cmd_seq.copy_texture_to_texture(
+                &source,
+                0,
+                Origin3d { x: 2, y: 0, z: 0 },
+                &destination,
+                0,
+                Origin3d::ZERO,
+                extent,
+            ),
+            eq(Err(CopyError::SourceOutOfBounds))
+        );
+        expect_that!(
+            &cmd_seq.copy_texture_to_texture(
+                &source,
+                1,
+                Origin3d::ZERO,
+                &destination,
+                0,
+                Origin3d::ZERO,
+                extent,
+            ),
+            eq(Err(CopyError::SourceOutOfBounds))
+        );
+        expect_that!(
+            &cmd_seq.copy_texture_to_texture(
+                &destination,
+                0,
+                Origin3d::ZERO,
+                &source,
+                0,
+                Origin3d::ZERO,
+                extent,
+            ),
+            eq(Err(CopyError::MissingTextureUsage(TextureUsage::COPY_DST)))
+        );
+        cmd_seq.submit(&instance);
+
+        let bytes = destination.read_bytes(&instance, 0, Origin3d::ZERO, *destination.size());
+        expect_that!(&bytes[8..12].to_vec(), eq(vec![12, 13, 14, 15]));
+    }
+
+    #[test]
+    fn copy_texture_to_texture_incompatible_formats() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let source = create_texture(&instance, 2, 2, TextureUsage::COPY_SRC);
+        let destination = Texture::new(
+            &instance,
+            &TextureDescriptor {
+                label: None,
+                size: *source.size(),
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Bgra8Unorm,
+                usage: TextureUsage::COPY_DST,
+            },
+        );
+        let mut cmd_seq = CommandSequence::new(&instance);
+        expect_that!(
+            &cmd_seq.copy_texture_to_texture(
+                &source,
+                0,
+                Origin3d::ZERO,
+                &destination,
+                0,
+                Origin3d::ZERO,
+                *source.size(),
+            ),
+            eq(Err(CopyError::IncompatibleFormats {
+                source: TextureFormat::Rgba8Unorm,
+                destination: TextureFormat::Bgra8Unorm,
+            }))
+        );
+    }
+
+    #[test]
+    fn blit() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let mut source = create_canvas(
+            &instance,
+            CanvasSize::new(2, 2),
+            CanvasColorBufferUsage::SAMPLED,
+        );
+        let mut target = create_canvas(
+            &instance,
+            CanvasSize::new(8, 6),
+            CanvasColorBufferUsage::COPY_SRC,
+        );
+        let requirements = source.render_pass_requirements();
+
+        let mut cmd_seq = CommandSequence::new(&instance);
+        {
+            let frame = source.current_frame(&instance).unwrap();
+            let _rpass = cmd_seq
+                .begin_render_pass(
+                    &frame,
+                    &requirements,
+                    &RenderPassOperations {
+                        color_operations: vec![ColorOperations {
+                            load: LoadOp::Clear(ColorF64::RED),
+                            store: true,
+                        }],
+                        ..RenderPassOperations::default()
+                    },
+                )
+                .unwrap();
+        }
+        {
+            let frame = target.current_frame(&instance).unwrap();
+            cmd_seq
+                .blit(&instance, &source, 0, &frame, FilterMode::Linear)
+                .unwrap();
+        }
+        cmd_seq.submit(&instance);
+
+        let image = target.color_texture(0).unwrap().to_image(&instance);
+        expect_that!(&image.dimensions(), eq((8, 6)));
+        for pixel in image.pixels() {
+            expect_that!(&pixel.0, eq([255, 0, 0, 255]));
+        }
+    }
+
+    #[test]
+    fn blit_errors() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let source = create_canvas(
+            &instance,
+            CanvasSize::new(2, 2),
+            CanvasColorBufferUsage::COPY_SRC,
+        );
+        let mut target = create_canvas(
+            &instance,
+            CanvasSize::new(2, 2),
+            CanvasColorBufferUsage::empty(),
+        );
+
+        let mut cmd_seq = CommandSequence::new(&instance);
+        let frame = target.current_frame(&instance).unwrap();
+        expect_that!(
+            &cmd_seq.blit(&instance, &source, 0, &frame, FilterMode::Linear),
+            eq(Err(CopyError::MissingColorBufferUsage(
+                CanvasColorBufferUsage::SAMPLED
+            )))
+        );
+        expect_that!(
+            &cmd_seq.blit(&instance, &source, 1, &frame, FilterMode::Linear),
+            eq(Err(CopyError::MissingColorBuffer { index: 1 }))
+        );
+    }
+
+    #[test]
+    fn blit_unfilterable_format() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let source = CanvasTexture::new(
+            &instance,
+            &CanvasTextureDescriptor {
+                size: CanvasSize::new(2, 2),
+                color_buffer_descriptors: vec![CanvasTextureColorBufferDescriptor {
+                    format: CanvasColorBufferFormat::Rgba32Float,
+                    usage: CanvasColorBufferUsage::SAMPLED,
+                }],
+                ..CanvasTextureDescriptor::default()
+            },
+        )
+        .unwrap();
+        let mut target = create_canvas(
+            &instance,
+            CanvasSize::new(4, 4),
+            CanvasColorBufferUsage::empty(),
+        );
+
+        let mut cmd_seq = CommandSequence::new(&instance);
+        let frame = target.current_frame(&instance).unwrap();
+        expect_that!(
+            &cmd_seq.blit(&instance, &source, 0, &frame, FilterMode::Linear),
+            eq(Err(CopyError::UnfilterableFormat(
+                TextureFormat::Rgba32Float
+            )))
+        );
+        expect_that!(&cmd_seq
+            .blit(&instance, &source, 0, &frame, FilterMode::Nearest)
+            .is_ok());
     }
 }
@@ -0,0 +1,53 @@
+use std::sync::Mutex;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    message: String,
+}
+
+impl ValidationError {
+    pub fn new<S: Into<String>>(message: S) -> Self {
+        Self {
+            message: message.into(),
+        }
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Validation error ({})", self.message)
+    }
+}
+
+impl std::error::Error for ValidationError {}
+
+// wgpu 0.6 has no error scopes: only the errors detected by the checks of the
+// try_new constructors are reported, wgpu still panics on any other error.
+// Each scope keeps the first error reported while it is the innermost one.
+#[derive(Debug, Default)]
+pub(crate) struct ErrorScopeStack {
+    scopes: Mutex<Vec<Option<ValidationError>>>,
+}
+
+impl ErrorScopeStack {
+    pub fn push(&self) {
+        self.scopes.lock().unwrap().push(None);
+    }
+
+    pub fn pop(&self) -> Option<Result<(), ValidationError>> {
+        self.scopes.lock().unwrap().pop().map(|scope| match scope {
+            Some(error) => Err(error),
+            None => Ok(()),
+        })
+    }
+
+    pub fn report(&self, error: &ValidationError) {
+        if let Some(scope @ None) = self.scopes.lock().unwrap().last_mut() {
+            *scope = Some(error.clone());
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::time::Duration;
+
+use super::Instance;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PassTiming {
+    pub name: String,
+    pub duration: Duration,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ProfilingError {
+    NotEnabled,
+    TimestampQueriesNotSupported,
+}
+
+impl std::fmt::Display for ProfilingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ProfilingError::NotEnabled => write!(f, "Profiling isn't enabled"),
+            ProfilingError::TimestampQueriesNotSupported => {
+                write!(f, "Timestamp queries aren't supported")
+            }
+        }
+    }
+}
+
+impl std::error::Error for ProfilingError {}
+
+// Keeps track of the passes recorded in a command sequence, so that their GPU
+// durations can be reported after submission.
+#[derive(Debug)]
+pub(crate) struct GpuProfiler {
+    timestamps_supported: bool,
+    pass_names: Vec<String>,
+    next_pass_name: Option<String>,
+}
+
+impl GpuProfiler {
+    pub fn new(instance: &Instance) -> Self {
+        Self {
+            timestamps_supported: instance.supports_timestamp_queries(),
+            pass_names: Vec::new(),
+            next_pass_name: None,
+        }
+    }
+
+    pub fn timestamps_supported(&self) -> bool {
+        self.timestamps_supported
+    }
+
+    pub fn pass_names(&self) -> &[String] {
+        &self.pass_names
+    }
+
+    pub fn name_next_pass(&mut self, name: String) {
+        self.next_pass_name = Some(name);
+    }
+
+    pub fn begin_pass(&mut self, kind: &str) {
+        let name = match self.next_pass_name.take() {
+            Some(name) => name,
+            None => format!("{} pass {}", kind, self.pass_names.len()),
+        };
+        self.pass_names.push(name);
+    }
+
+    pub fn resolve(self) -> Result<Vec<PassTiming>, ProfilingError> {
+        // No timestamp is written when queries aren't supported, which is
+        // always the case with wgpu 0.6.
+        if !self.timestamps_supported {
+            return Err(ProfilingError::TimestampQueriesNotSupported);
+        }
+        Ok(Vec::new())
+    }
+}
@@ -1,6 +1,13 @@
 use std::{
+    collections::HashMap,
     default::Default,
-    ops::{Deref, DerefMut},
+    num::NonZeroU32,
+    ops::{Deref, DerefMut, Range},
+    path::PathBuf,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
 };
 
 use rae_app::window::Window;
@@ -10,13 +17,19 @@ use wgpu::util::DeviceExt;
 use raw_window_handle::HasRawWindowHandle;
 
 use super::{
-    AdapterInfo, Backend, BindGroupDescriptor, BindGroupLayoutDescriptor, BufferAddress,
-    BufferCopyView, BufferDescriptor, BufferInitDescriptor, BufferUsage, ColorF64, CommandBuffer,
-    CommandEncoderDescriptor, Extent3d, Features, Limits, Maintain, MapMode, Operations, Origin3d,
-    PipelineLayoutDescriptor, PowerPreference, RenderBundleEncoderDescriptor,
-    RenderPipelineDescriptor, SamplerDescriptor, ShaderModuleSource, SwapChainDescriptor,
+    validate_bind_group, validate_bind_group_layout, validate_buffer, validate_buffer_usage,
+    validate_compute_pipeline, validate_pipeline_layout, validate_render_pipeline,
+    validate_sampler, validate_shader_module, validate_texture, AdapterInfo, Backend,
+    BindGroupDescriptor, BindGroupLayoutDescriptor, BlitPipeline, BufferAddress, BufferCopyView,
+    BufferDescriptor, BufferInitDescriptor, BufferSlice, BufferUsage, ColorF64, CommandBuffer,
+    CommandEncoderDescriptor, ComputePipelineDescriptor, DeviceType, DynamicOffset,
+    ErrorScopeStack, Extent3d, Features, FilterMode, Limits, Maintain, MapMode, Operations,
+    Origin3d, PipelineLayoutDescriptor, PowerPreference, RenderBundleDescriptor,
+    RenderBundleEncoderDescriptor, RenderPass, RenderPassRequirements, RenderPipelineDescriptor,
+    SamplerDescriptor, ShaderModuleSource, ShaderStage, StagingBufferPool, SwapChainDescriptor,
     TextureCopyView, TextureDataLayout, TextureDescriptor, TextureDimension, TextureFormat,
-    TextureUsage,
+    TextureReadback, TextureUsage, TextureView, TextureViewDescriptor, TextureViewDimension,
+    ValidationError,
 };
 
 pub type SampleCount = u32;
@@ -24,13 +37,41 @@ pub type ColorOperations = Operations<ColorF64>;
 pub type DepthOperations = Operations<f32>;
 pub type StencilOperations = Operations<u32>;
 
+#[derive(Debug, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
+pub enum AdapterSelection {
+    // The adapter chosen by wgpu according to the power preference.
+    Preferred,
+    // Index in the list returned by Instance::enumerate_adapters.
+    Index(usize),
+    Vendor(usize),
+    DeviceType(DeviceType),
+}
+
+// DeviceType only implements PartialEq, but its comparison is total.
+impl Eq for AdapterSelection {}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct AdapterDescription {
+    pub info: AdapterInfo,
+    pub features: Features,
+    pub limits: Limits,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone, serde::Serialize, serde::Deserialize)]
 pub struct InstanceDescriptor {
     pub backend: Backend,
     pub power_preference: PowerPreference,
+    // Only the preferred selection can be used with a compatible surface.
+    pub adapter_selection: AdapterSelection,
+    // Forces the selection of a software (CPU) adapter, ignoring the adapter
+    // selection.
+    pub force_fallback_adapter: bool,
     pub required_features: Features,
     pub optional_features: Features,
     pub required_limits: Limits,
+    // Directory where all API calls are recorded, to be replayed with
+    // replay_trace (requires the replay feature).
+    pub trace_path: Option<PathBuf>,
 }
 
 impl InstanceDescriptor {
@@ -40,9 +81,12 @@ impl InstanceDescriptor {
         Self {
             backend: Backend::PRIMARY,
             power_preference: PowerPreference::HighPerformance,
-            required_features: Features::default() | Features::PUSH_CONSTANTS,
-            optional_features: Features::empty(),
+            adapter_selection: AdapterSelection::Preferred,
+            force_fallback_adapter: false,
+            required_features: Features::default(),
+            optional_features: Features::PUSH_CONSTANTS | Features::TEXTURE_COMPRESSION_BC,
             required_limits,
+            trace_path: None,
         }
     }
 }
@@ -54,27 +98,46 @@ impl Default for InstanceDescriptor {
         Self {
             backend: Backend::PRIMARY,
             power_preference: PowerPreference::Default,
-            required_features: Features::default() | Features::PUSH_CONSTANTS,
-            optional_features: Features::empty(),
+            adapter_selection: AdapterSelection::Preferred,
+            force_fallback_adapter: false,
+            required_features: Features::default(),
+            optional_features: Features::PUSH_CONSTANTS | Features::TEXTURE_COMPRESSION_BC,
             required_limits,
+            trace_path: None,
         }
     }
 }
 
 #[derive(Debug)]
 pub struct Instance {
-    queue: wgpu::Queue,
-    device: wgpu::Device,
+    descriptor: InstanceDescriptor,
+    generation: u64,
+    device_lost: AtomicBool,
+    error_scopes: ErrorScopeStack,
+    submission_count: Arc<AtomicU64>,
+    blit_pipelines: Mutex<HashMap<(TextureFormat, SampleCount, FilterMode), Arc<BlitPipeline>>>,
+    queue: Arc<wgpu::Queue>,
+    device: Arc<wgpu::Device>,
     adapter: wgpu::Adapter,
     instance: wgpu::Instance,
 }
 
 impl Instance {
     pub fn new(desc: &InstanceDescriptor) -> Result<Self, InstanceCreationError> {
+        futures::executor::block_on(Self::new_async(desc))
+    }
+
+    pub async fn new_async(desc: &InstanceDescriptor) -> Result<Self, InstanceCreationError> {
         let instance = Self::create_instance(desc);
-        let adapter = Self::create_adapter(&instance, desc, None)?;
-        let (device, queue) = Self::create_device_and_queue(&adapter, desc)?;
+        let adapter = Self::create_adapter(&instance, desc, None).await?;
+        let (device, queue) = Self::create_device_and_queue(&adapter, desc).await?;
         Ok(Self {
+            descriptor: desc.clone(),
+            generation: 0,
+            device_lost: AtomicBool::new(false),
+            error_scopes: ErrorScopeStack::default(),
+            submission_count: Arc::new(AtomicU64::new(0)),
+            blit_pipelines: Mutex::new(HashMap::new()),
             queue,
             adapter,
             device,
@@ -86,13 +149,30 @@ impl Instance {
     pub unsafe fn new_with_compatible_window(
         desc: &InstanceDescriptor,
         compatible_window: &Window,
+    ) -> Result<(Self, Surface), InstanceCreationError> {
+        futures::executor::block_on(Self::new_with_compatible_window_async(
+            desc,
+            compatible_window,
+        ))
+    }
+
+    // Unsafe: surface creation.
+    pub async unsafe fn new_with_compatible_window_async(
+        desc: &InstanceDescriptor,
+        compatible_window: &Window,
     ) -> Result<(Self, Surface), InstanceCreationError> {
         let instance = Self::create_instance(desc);
         let surface = instance.create_surface(compatible_window);
-        let adapter = Self::create_adapter(&instance, desc, Some(&surface))?;
-        let (device, queue) = Self::create_device_and_queue(&adapter, desc)?;
+        let adapter = Self::create_adapter(&instance, desc, Some(&surface)).await?;
+        let (device, queue) = Self::create_device_and_queue(&adapter, desc).await?;
         Ok((
             Self {
+                descriptor: desc.clone(),
+                generation: 0,
+                device_lost: AtomicBool::new(false),
+                error_scopes: ErrorScopeStack::default(),
+                submission_count: Arc::new(AtomicU64::new(0)),
+                blit_pipelines: Mutex::new(HashMap::new()),
                 queue,
                 adapter,
                 device,
@@ -102,6 +182,65 @@ impl Instance {
         ))
     }
 
+    // Requests a new adapter, device and queue with the original descriptor.
+    // wgpu 0.6 doesn't report device loss, see is_device_lost for when it is
+    // assumed. Every resource created from this instance
+    // becomes invalid and must be rebuilt: dependents can compare generations
+    // to detect it. Surfaces stay valid, as the underlying wgpu instance is
+    // kept.
+    pub fn recreate(
+        &mut self,
+        compatible_surface: Option<&Surface>,
+    ) -> Result<(), InstanceCreationError> {
+        futures::executor::block_on(self.recreate_async(compatible_surface))
+    }
+
+    pub async fn recreate_async(
+        &mut self,
+        compatible_surface: Option<&Surface>,
+    ) -> Result<(), InstanceCreationError> {
+        let adapter = Self::create_adapter(
+            &self.instance,
+            &self.descriptor,
+            compatible_surface.map(|x| &x.value),
+        )
+        .await?;
+        let (device, queue) = Self::create_device_and_queue(&adapter, &self.descriptor).await?;
+        self.blit_pipelines.get_mut().unwrap().clear();
+        self.queue = queue;
+        self.device = device;
+        self.adapter = adapter;
+        self.generation += 1;
+        self.device_lost.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    // Set when a canvas window can't get a frame because its swap chain is
+    // still lost after being recreated or the device is out of memory, or
+    // when marked by the application. Cleared when the instance is recreated.
+    pub fn is_device_lost(&self) -> bool {
+        self.device_lost.load(Ordering::Relaxed)
+    }
+
+    pub fn mark_device_lost(&self) {
+        self.device_lost.store(true, Ordering::Relaxed);
+    }
+
+    pub fn enumerate_adapters(backend: Backend) -> Vec<AdapterDescription> {
+        wgpu::Instance::new(backend)
+            .enumerate_adapters(backend)
+            .map(|adapter| AdapterDescription {
+                info: adapter.get_info(),
+                features: adapter.features(),
+                limits: adapter.limits(),
+            })
+            .collect()
+    }
+
     pub fn info(&self) -> AdapterInfo {
         self.adapter.get_info()
     }
@@ -118,8 +257,66 @@ impl Instance {
         self.device.limits()
     }
 
+    // Validation errors detected by the try_new constructors are reported to
+    // the innermost error scope. wgpu 0.6 has no error scopes, so any error
+    // not detected by those checks still makes wgpu panic, e.g. shader entry
+    // points or interfaces not matching the pipeline.
+    pub fn push_error_scope(&self) {
+        self.error_scopes.push();
+    }
+
+    // Returns the first validation error reported since the matching
+    // push_error_scope call, or None if there is no scope to pop.
+    pub fn pop_error_scope(&self) -> Option<Result<(), ValidationError>> {
+        self.error_scopes.pop()
+    }
+
+    pub(crate) fn create_validated<T, F: FnOnce() -> T>(
+        &self,
+        validation: Result<(), ValidationError>,
+        create: F,
+    ) -> Result<T, ValidationError> {
+        match validation {
+            Ok(()) => Ok(create()),
+            Err(error) => {
+                self.error_scopes.report(&error);
+                Err(error)
+            }
+        }
+    }
+
+    // wgpu 0.6 doesn't expose timestamp queries.
+    pub fn supports_timestamp_queries(&self) -> bool {
+        false
+    }
+
     pub fn submit<I: IntoIterator<Item = CommandBuffer>>(&self, command_buffers: I) {
         self.queue.submit(command_buffers);
+        self.submission_count.fetch_add(1, Ordering::Relaxed);
+    }
+
+    // The handle keeps referring to the current device when the instance is
+    // recreated, objects holding it must then be rebuilt.
+    pub(crate) fn device_handle(&self) -> DeviceHandle {
+        DeviceHandle {
+            device: Arc::clone(&self.device),
+            queue: Arc::clone(&self.queue),
+            submission_count: Arc::clone(&self.submission_count),
+        }
+    }
+
+    // Blit pipelines are cached, as blits are typically performed every frame.
+    pub(crate) fn blit_pipeline(
+        &self,
+        format: TextureFormat,
+        sample_count: SampleCount,
+        filter: FilterMode,
+    ) -> Arc<BlitPipeline> {
+        let mut blit_pipelines = self.blit_pipelines.lock().unwrap();
+        let pipeline = blit_pipelines
+            .entry((format, sample_count, filter))
+            .or_insert_with(|| Arc::new(BlitPipeline::new(self, format, sample_count, filter)));
+        Arc::clone(pipeline)
     }
 
     pub fn write_buffer(&self, buffer: &Buffer, offset: BufferAddress, data: &[u8]) {
@@ -140,17 +337,46 @@ impl Instance {
         wgpu::Instance::new(desc.backend)
     }
 
-    fn create_adapter(
+    async fn create_adapter(
         instance: &wgpu::Instance,
         desc: &InstanceDescriptor,
         compatible_surface: Option<&wgpu::Surface>,
     ) -> Result<wgpu::Adapter, InstanceCreationError> {
-        let adapter = match futures::executor::block_on(instance.request_adapter(
-            &wgpu::RequestAdapterOptions {
-                power_preference: desc.power_preference,
-                compatible_surface,
-            },
-        )) {
+        // wgpu 0.6 can only check surface compatibility when it chooses the
+        // adapter itself, explicit selections are rejected.
+        if compatible_surface.is_some()
+            && (desc.force_fallback_adapter
+                || desc.adapter_selection != AdapterSelection::Preferred)
+        {
+            return Err(InstanceCreationError::IncompatibleAdapterSelection);
+        }
+        let find_adapter = |predicate: &dyn Fn(&AdapterInfo) -> bool| {
+            instance
+                .enumerate_adapters(desc.backend)
+                .find(|adapter| predicate(&adapter.get_info()))
+        };
+        let adapter = if desc.force_fallback_adapter {
+            find_adapter(&|info| info.device_type == DeviceType::Cpu)
+        } else {
+            match &desc.adapter_selection {
+                AdapterSelection::Preferred => {
+                    instance
+                        .request_adapter(&wgpu::RequestAdapterOptions {
+                            power_preference: desc.power_preference,
+                            compatible_surface,
+                        })
+                        .await
+                }
+                AdapterSelection::Index(index) => {
+                    instance.enumerate_adapters(desc.backend).nth(*index)
+                }
+                AdapterSelection::Vendor(vendor) => find_adapter(&|info| info.vendor == *vendor),
+                AdapterSelection::DeviceType(device_type) => {
+                    find_adapter(&|info| info.device_type == *device_type)
+                }
+            }
+        };
+        let adapter = match adapter {
             Some(v) => v,
             None => return Err(InstanceCreationError::AdapterRequestFailed),
         };
@@ -164,19 +390,29 @@ impl Instance {
         Ok(adapter)
     }
 
-    fn create_device_and_queue(
+    async fn create_device_and_queue(
         adapter: &wgpu::Adapter,
         desc: &InstanceDescriptor,
-    ) -> Result<(wgpu::Device, wgpu::Queue), InstanceCreationError> {
-        let (device, queue) = futures::executor::block_on(adapter.request_device(
-            &wgpu::DeviceDescriptor {
-                features: (desc.optional_features & adapter.features()) | desc.required_features,
-                limits: desc.required_limits.clone(),
-                shader_validation: true,
-            },
-            None,
-        ))?;
-        Ok((device, queue))
+    ) -> Result<(Arc<wgpu::Device>, Arc<wgpu::Queue>), InstanceCreationError> {
+        if let Some(trace_path) = &desc.trace_path {
+            if let Err(e) = std::fs::create_dir_all(trace_path) {
+                return Err(InstanceCreationError::TraceDirectoryCreationFailed(
+                    e.kind(),
+                ));
+            }
+        }
+        let (device, queue) = adapter
+            .request_device(
+                &wgpu::DeviceDescriptor {
+                    features: (desc.optional_features & adapter.features())
+                        | desc.required_features,
+                    limits: desc.required_limits.clone(),
+                    shader_validation: true,
+                },
+                desc.trace_path.as_deref(),
+            )
+            .await?;
+        Ok((Arc::new(device), Arc::new(queue)))
     }
 }
 
@@ -211,6 +447,15 @@ impl ShaderModule {
             value: instance.device.create_shader_module(source),
         }
     }
+
+    pub fn try_new(
+        instance: &Instance,
+        source: ShaderModuleSource,
+    ) -> Result<Self, ValidationError> {
+        instance.create_validated(validate_shader_module(&source), || {
+            Self::new(instance, source)
+        })
+    }
 }
 
 impl Deref for ShaderModule {
@@ -237,6 +482,15 @@ impl PipelineLayout {
             value: instance.device.create_pipeline_layout(desc),
         }
     }
+
+    pub fn try_new(
+        instance: &Instance,
+        desc: &PipelineLayoutDescriptor,
+    ) -> Result<Self, ValidationError> {
+        instance.create_validated(validate_pipeline_layout(instance, desc), || {
+            Self::new(instance, desc)
+        })
+    }
 }
 
 impl Deref for PipelineLayout {
@@ -263,6 +517,13 @@ impl RenderPipeline {
             value: instance.device.create_render_pipeline(desc),
         }
     }
+
+    pub fn try_new(
+        instance: &Instance,
+        desc: &RenderPipelineDescriptor,
+    ) -> Result<Self, ValidationError> {
+        instance.create_validated(validate_render_pipeline(desc), || Self::new(instance, desc))
+    }
 }
 
 impl Deref for RenderPipeline {
@@ -278,23 +539,123 @@ impl DerefMut for RenderPipeline {
     }
 }
 
+#[derive(Debug)]
+pub struct ComputePipeline {
+    value: wgpu::ComputePipeline,
+}
+
+impl ComputePipeline {
+    pub fn new(instance: &Instance, desc: &ComputePipelineDescriptor) -> Self {
+        Self {
+            value: instance.device.create_compute_pipeline(desc),
+        }
+    }
+
+    pub fn try_new(
+        instance: &Instance,
+        desc: &ComputePipelineDescriptor,
+    ) -> Result<Self, ValidationError> {
+        instance.create_validated(validate_compute_pipeline(desc), || {
+            Self::new(instance, desc)
+        })
+    }
+}
+
+impl Deref for ComputePipeline {
+    type Target = wgpu::ComputePipeline;
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl DerefMut for ComputePipeline {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+// Shared access to the device and queue, for objects that create resources or
+// write buffers while commands are being recorded, without access to the
+// instance.
+#[derive(Debug, Clone)]
+pub(crate) struct DeviceHandle {
+    device: Arc<wgpu::Device>,
+    queue: Arc<wgpu::Queue>,
+    submission_count: Arc<AtomicU64>,
+}
+
+impl DeviceHandle {
+    // Buffer writes are performed at the next submission, this number
+    // identifies it.
+    pub(crate) fn submission_count(&self) -> u64 {
+        self.submission_count.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn write_buffer(&self, buffer: &Buffer, offset: BufferAddress, data: &[u8]) {
+        self.queue.write_buffer(buffer, offset, data);
+    }
+
+    pub(crate) fn create_buffer(&self, desc: &BufferDescriptor) -> Buffer {
+        Buffer {
+            value: self.device.create_buffer(desc),
+            size: desc.size,
+            usage: desc.usage,
+        }
+    }
+
+    pub(crate) fn create_bind_group(&self, desc: &BindGroupDescriptor) -> BindGroup {
+        BindGroup {
+            value: self.device.create_bind_group(desc),
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct Buffer {
     value: wgpu::Buffer,
+    size: BufferAddress,
+    usage: BufferUsage,
 }
 
 impl Buffer {
     pub fn new(instance: &Instance, desc: &BufferDescriptor) -> Self {
         Self {
             value: instance.device.create_buffer(desc),
+            size: desc.size,
+            usage: desc.usage,
         }
     }
 
+    pub fn try_new(instance: &Instance, desc: &BufferDescriptor) -> Result<Self, ValidationError> {
+        instance.create_validated(validate_buffer(instance, desc), || {
+            Self::new(instance, desc)
+        })
+    }
+
     pub fn init(instance: &Instance, desc: &BufferInitDescriptor) -> Self {
         Self {
             value: instance.device.create_buffer_init(desc),
+            size: desc.contents.len() as BufferAddress,
+            usage: desc.usage,
         }
     }
+
+    pub fn try_init(
+        instance: &Instance,
+        desc: &BufferInitDescriptor,
+    ) -> Result<Self, ValidationError> {
+        instance.create_validated(validate_buffer_usage(instance, desc.usage), || {
+            Self::init(instance, desc)
+        })
+    }
+
+    pub fn size(&self) -> BufferAddress {
+        self.size
+    }
+
+    pub fn usage(&self) -> BufferUsage {
+        self.usage
+    }
 }
 
 impl Deref for Buffer {
@@ -351,6 +712,37 @@ impl<'a> RenderBundleEncoder<'a> {
             value: instance.device.create_render_bundle_encoder(desc),
         }
     }
+
+    // The resulting bundle can be executed in render passes with the given
+    // requirements.
+    pub fn from_render_pass_requirements(
+        instance: &'a Instance,
+        label: Option<&str>,
+        requirements: &RenderPassRequirements,
+    ) -> Self {
+        let color_formats: Vec<TextureFormat> = requirements
+            .color_buffer_formats
+            .iter()
+            .map(|x| TextureFormat::from(*x))
+            .collect();
+        Self::new(
+            instance,
+            &RenderBundleEncoderDescriptor {
+                label,
+                color_formats: &color_formats,
+                depth_stencil_format: requirements
+                    .depth_stencil_buffer_format
+                    .map(TextureFormat::from),
+                sample_count: requirements.sample_count,
+            },
+        )
+    }
+
+    pub fn finish(self, desc: &RenderBundleDescriptor) -> RenderBundle {
+        RenderBundle {
+            value: self.value.finish(desc),
+        }
+    }
 }
 
 impl<'a> Deref for RenderBundleEncoder<'a> {
@@ -366,6 +758,146 @@ impl<'a> DerefMut for RenderBundleEncoder<'a> {
     }
 }
 
+#[derive(Debug)]
+pub struct RenderBundle {
+    value: wgpu::RenderBundle,
+}
+
+impl Deref for RenderBundle {
+    type Target = wgpu::RenderBundle;
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl DerefMut for RenderBundle {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
+// Commands shared by render passes and render bundle encoders, so that draw
+// code can be written once for both.
+pub trait RenderEncoder<'a> {
+    fn set_pipeline(&mut self, pipeline: &'a wgpu::RenderPipeline);
+    fn set_bind_group(
+        &mut self,
+        index: u32,
+        bind_group: &'a wgpu::BindGroup,
+        offsets: &[DynamicOffset],
+    );
+    fn set_index_buffer(&mut self, buffer_slice: BufferSlice<'a>);
+    fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: BufferSlice<'a>);
+    fn set_push_constants(&mut self, stages: ShaderStage, offset: u32, data: &[u32]);
+    fn draw(&mut self, vertices: Range<u32>, instances: Range<u32>);
+    fn draw_indexed(&mut self, indices: Range<u32>, base_vertex: i32, instances: Range<u32>);
+    fn is_render_bundle_encoder(&self) -> bool;
+}
+
+impl<'a> RenderEncoder<'a> for RenderPass<'a> {
+    fn set_pipeline(&mut self, pipeline: &'a wgpu::RenderPipeline) {
+        RenderPass::set_pipeline(self, pipeline)
+    }
+
+    fn set_bind_group(
+        &mut self,
+        index: u32,
+        bind_group: &'a wgpu::BindGroup,
+        offsets: &[DynamicOffset],
+    ) {
+        RenderPass::set_bind_group(self, index, bind_group, offsets)
+    }
+
+    fn set_index_buffer(&mut self, buffer_slice: BufferSlice<'a>) {
+        RenderPass::set_index_buffer(self, buffer_slice)
+    }
+
+    fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: BufferSlice<'a>) {
+        RenderPass::set_vertex_buffer(self, slot, buffer_slice)
+    }
+
+    fn set_push_constants(&mut self, stages: ShaderStage, offset: u32, data: &[u32]) {
+        RenderPass::set_push_constants(self, stages, offset, data)
+    }
+
+    fn draw(&mut self, vertices: Range<u32>, instances: Range<u32>) {
+        RenderPass::draw(self, vertices, instances)
+    }
+
+    fn draw_indexed(&mut self, indices: Range<u32>, base_vertex: i32, instances: Range<u32>) {
+        RenderPass::draw_indexed(self, indices, base_vertex, instances)
+    }
+
+    fn is_render_bundle_encoder(&self) -> bool {
+        false
+    }
+}
+
+impl<'a> RenderEncoder<'a> for RenderBundleEncoder<'a> {
+    fn set_pipeline(&mut self, pipeline: &'a wgpu::RenderPipeline) {
+        self.value.set_pipeline(pipeline)
+    }
+
+    fn set_bind_group(
+        &mut self,
+        index: u32,
+        bind_group: &'a wgpu::BindGroup,
+        offsets: &[DynamicOffset],
+    ) {
+        self.value.set_bind_group(index, bind_group, offsets)
+    }
+
+    fn set_index_buffer(&mut self, buffer_slice: BufferSlice<'a>) {
+        self.value.set_index_buffer(buffer_slice)
+    }
+
+    fn set_vertex_buffer(&mut self, slot: u32, buffer_slice: BufferSlice<'a>) {
+        self.value.set_vertex_buffer(slot, buffer_slice)
+    }
+
+    fn set_push_constants(&mut self, stages: ShaderStage, offset: u32, data: &[u32]) {
+        self.value.set_push_constants(stages, offset, data)
+    }
+
+    fn draw(&mut self, vertices: Range<u32>, instances: Range<u32>) {
+        self.value.draw(vertices, instances)
+    }
+
+    fn draw_indexed(&mut self, indices: Range<u32>, base_vertex: i32, instances: Range<u32>) {
+        self.value.draw_indexed(indices, base_vertex, instances)
+    }
+
+    fn is_render_bundle_encoder(&self) -> bool {
+        true
+    }
+}
+
+#[derive(Debug)]
+pub struct ComputePass<'a> {
+    value: wgpu::ComputePass<'a>,
+}
+
+impl<'a> ComputePass<'a> {
+    pub fn new(encoder: &'a mut CommandEncoder) -> Self {
+        Self {
+            value: encoder.value.begin_compute_pass(),
+        }
+    }
+}
+
+impl<'a> Deref for ComputePass<'a> {
+    type Target = wgpu::ComputePass<'a>;
+    fn deref(&self) -> &Self::Target {
+        &self.value
+    }
+}
+
+impl<'a> DerefMut for ComputePass<'a> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.value
+    }
+}
+
 #[derive(Debug)]
 pub struct BindGroupLayout {
     value: wgpu::BindGroupLayout,
@@ -377,6 +909,15 @@ impl BindGroupLayout {
             value: instance.device.create_bind_group_layout(desc),
         }
     }
+
+    pub fn try_new(
+        instance: &Instance,
+        desc: &BindGroupLayoutDescriptor,
+    ) -> Result<Self, ValidationError> {
+        instance.create_validated(validate_bind_group_layout(instance, desc), || {
+            Self::new(instance, desc)
+        })
+    }
 }
 
 impl Deref for BindGroupLayout {
@@ -403,6 +944,13 @@ impl BindGroup {
             value: instance.device.create_bind_group(desc),
         }
     }
+
+    pub fn try_new(
+        instance: &Instance,
+        desc: &BindGroupDescriptor,
+    ) -> Result<Self, ValidationError> {
+        instance.create_validated(validate_bind_group(desc), || Self::new(instance, desc))
+    }
 }
 
 impl Deref for BindGroup {
@@ -419,30 +967,115 @@ impl DerefMut for BindGroup {
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
-struct TextureBufferSize {
-    width: u64,
-    height: u64,
-    unpadded_bytes_per_row: u64,
-    padded_bytes_per_row: u64,
+pub struct TextureFormatBlock {
+    pub byte_count: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl From<TextureFormat> for TextureFormatBlock {
+    fn from(format: TextureFormat) -> Self {
+        let (byte_count, width, height) = match format {
+            TextureFormat::R8Unorm
+            | TextureFormat::R8Snorm
+            | TextureFormat::R8Uint
+            | TextureFormat::R8Sint => (1, 1, 1),
+            TextureFormat::R16Uint
+            | TextureFormat::R16Sint
+            | TextureFormat::R16Float
+            | TextureFormat::Rg8Unorm
+            | TextureFormat::Rg8Snorm
+            | TextureFormat::Rg8Uint
+            | TextureFormat::Rg8Sint => (2, 1, 1),
+            TextureFormat::R32Uint
+            | TextureFormat::R32Sint
+            | TextureFormat::R32Float
+            | TextureFormat::Rg16Uint
+            | TextureFormat::Rg16Sint
+            | TextureFormat::Rg16Float
+            | TextureFormat::Rgba8Unorm
+            | TextureFormat::Rgba8UnormSrgb
+            | TextureFormat::Rgba8Snorm
+            | TextureFormat::Rgba8Uint
+            | TextureFormat::Rgba8Sint
+            | TextureFormat::Bgra8Unorm
+            | TextureFormat::Bgra8UnormSrgb
+            | TextureFormat::Rgb10a2Unorm
+            | TextureFormat::Rg11b10Float
+            | TextureFormat::Depth32Float
+            | TextureFormat::Depth24Plus
+            | TextureFormat::Depth24PlusStencil8 => (4, 1, 1),
+            TextureFormat::Rg32Uint
+            | TextureFormat::Rg32Sint
+            | TextureFormat::Rg32Float
+            | TextureFormat::Rgba16Uint
+            | TextureFormat::Rgba16Sint
+            | TextureFormat::Rgba16Float => (8, 1, 1),
+            TextureFormat::Rgba32Uint | TextureFormat::Rgba32Sint | TextureFormat::Rgba32Float => {
+                (16, 1, 1)
+            }
+            TextureFormat::Bc1RgbaUnorm
+            | TextureFormat::Bc1RgbaUnormSrgb
+            | TextureFormat::Bc4RUnorm
+            | TextureFormat::Bc4RSnorm => (8, 4, 4),
+            TextureFormat::Bc2RgbaUnorm
+            | TextureFormat::Bc2RgbaUnormSrgb
+            | TextureFormat::Bc3RgbaUnorm
+            | TextureFormat::Bc3RgbaUnormSrgb
+            | TextureFormat::Bc5RgUnorm
+            | TextureFormat::Bc5RgSnorm
+            | TextureFormat::Bc6hRgbUfloat
+            | TextureFormat::Bc6hRgbSfloat
+            | TextureFormat::Bc7RgbaUnorm
+            | TextureFormat::Bc7RgbaUnormSrgb => (16, 4, 4),
+        };
+        Self {
+            byte_count,
+            width,
+            height,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) struct TextureBufferSize {
+    pub row_count: u64,
+    pub layer_count: u64,
+    pub unpadded_bytes_per_row: u64,
+    pub padded_bytes_per_row: u64,
 }
 
 impl TextureBufferSize {
-    pub fn new(width: u64, height: u64) -> Self {
-        let bytes_per_pixel = std::mem::size_of::<u32>() as u64;
-        let unpadded_bytes_per_row = width * bytes_per_pixel;
+    pub fn new(extent: &Extent3d, block: &TextureFormatBlock) -> Self {
+        let column_count = ((extent.width + block.width - 1) / block.width) as u64;
+        let row_count = ((extent.height + block.height - 1) / block.height) as u64;
+        let unpadded_bytes_per_row = column_count * block.byte_count as u64;
         let align = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as u64;
         let padded_bytes_per_row_padding = (align - unpadded_bytes_per_row % align) % align;
         let padded_bytes_per_row = unpadded_bytes_per_row + padded_bytes_per_row_padding;
         Self {
-            width,
-            height,
+            row_count,
+            layer_count: extent.depth as u64,
             unpadded_bytes_per_row,
             padded_bytes_per_row,
         }
     }
 
     pub fn byte_count(&self) -> u64 {
-        self.padded_bytes_per_row * self.height
+        self.padded_bytes_per_row * self.row_count * self.layer_count
+    }
+
+    pub fn unpad(&self, padded_bytes: &[u8]) -> Vec<u8> {
+        let mut unpadded_bytes = Vec::with_capacity(
+            (self.unpadded_bytes_per_row * self.row_count * self.layer_count) as usize,
+        );
+        for chunk in padded_bytes
+            .chunks(self.padded_bytes_per_row as usize)
+            .take((self.row_count * self.layer_count) as usize)
+        {
+            unpadded_bytes.extend_from_slice(&chunk[..self.unpadded_bytes_per_row as usize]);
+        }
+        unpadded_bytes
     }
 }
 
@@ -450,6 +1083,10 @@ impl TextureBufferSize {
 pub struct Texture {
     value: wgpu::Texture,
     size: Extent3d,
+    mip_level_count: u32,
+    dimension: TextureDimension,
+    format: TextureFormat,
+    usage: TextureUsage,
 }
 
 impl Texture {
@@ -457,49 +1094,60 @@ impl Texture {
         Self {
             value: instance.device.create_texture(desc),
             size: desc.size,
+            mip_level_count: desc.mip_level_count,
+            dimension: desc.dimension,
+            format: desc.format,
+            usage: desc.usage,
         }
     }
 
+    pub fn try_new(instance: &Instance, desc: &TextureDescriptor) -> Result<Self, ValidationError> {
+        instance.create_validated(validate_texture(instance, desc), || {
+            Self::new(instance, desc)
+        })
+    }
+
     pub fn size(&self) -> &Extent3d {
         &self.size
     }
 
-    pub fn from_image(instance: &Instance, img: &image::RgbaImage, usage: TextureUsage) -> Self {
-        let img_dimensions = img.dimensions();
-        let size = Extent3d {
-            width: img_dimensions.0,
-            height: img_dimensions.1,
-            depth: 1,
+    pub fn mip_level_count(&self) -> u32 {
+        self.mip_level_count
+    }
+
+    pub fn dimension(&self) -> TextureDimension {
+        self.dimension
+    }
+
+    pub fn format(&self) -> TextureFormat {
+        self.format
+    }
+
+    pub fn usage(&self) -> TextureUsage {
+        self.usage
+    }
+
+    pub fn mip_level_size(&self, mip_level: u32) -> Extent3d {
+        assert!(mip_level < self.mip_level_count, "Invalid mip level");
+        let depth = match self.dimension {
+            TextureDimension::D3 => std::cmp::max(1, self.size.depth >> mip_level),
+            _ => self.size.depth,
         };
-        let texture = Self::new(
-            instance,
-            &TextureDescriptor {
-                label: None,
-                size,
-                mip_level_count: 1,
-                sample_count: 1,
-                dimension: TextureDimension::D2,
-                format: TextureFormat::Rgba8UnormSrgb,
-                usage: usage | TextureUsage::COPY_DST,
-            },
-        );
-        texture.write(
-            instance,
-            0,
-            Origin3d::ZERO,
-            img.as_flat_samples().as_slice(),
-            TextureDataLayout {
-                offset: 0,
-                bytes_per_row: 4 * size.height,
-                rows_per_image: 0,
-            },
-            size,
-        );
-        texture
+        Extent3d {
+            width: std::cmp::max(1, self.size.width >> mip_level),
+            height: std::cmp::max(1, self.size.height >> mip_level),
+            depth,
+        }
     }
 
-    pub fn to_image(&self, instance: &Instance) -> image::RgbaImage {
-        let buffer_size = TextureBufferSize::new(self.size.width as u64, self.size.height as u64);
+    pub fn read_bytes(
+        &self,
+        instance: &Instance,
+        mip_level: u32,
+        origin: Origin3d,
+        extent: Extent3d,
+    ) -> Vec<u8> {
+        let buffer_size = self.read_buffer_size(mip_level, origin, extent);
         let output_buffer = Buffer::new(
             instance,
             &BufferDescriptor {
@@ -509,51 +1157,103 @@ impl Texture {
                 mapped_at_creation: false,
             },
         );
-        let mut encoder = CommandEncoder::new(instance, &CommandEncoderDescriptor::default());
-        {
-            encoder.copy_texture_to_buffer(
-                TextureCopyView {
-                    texture: &self.value,
-                    mip_level: 0,
-                    origin: Origin3d::ZERO,
-                },
-                BufferCopyView {
-                    buffer: &output_buffer,
-                    layout: TextureDataLayout {
-                        offset: 0,
-                        bytes_per_row: buffer_size.padded_bytes_per_row as u32,
-                        rows_per_image: 0,
-                    },
-                },
-                *self.size(),
-            );
-        }
-        instance.submit(Some(encoder.finish()));
+        self.copy_to_buffer(
+            instance,
+            mip_level,
+            origin,
+            extent,
+            &output_buffer,
+            &buffer_size,
+        );
 
         let buffer_slice = output_buffer.slice(..);
         let buffer_future = buffer_slice.map_async(MapMode::Read);
         instance.poll(Maintain::Wait);
 
-        let future_image = async {
+        let future_bytes = async {
             buffer_future.await.unwrap();
             let padded_buffer = buffer_slice.get_mapped_range();
-
-            let mut unpadded_buffer =
-                Vec::with_capacity((self.size.width * self.size.height) as usize);
-            for chunk in padded_buffer.chunks(buffer_size.padded_bytes_per_row as usize) {
-                unpadded_buffer
-                    .extend_from_slice(&chunk[..buffer_size.unpadded_bytes_per_row as usize]);
-            }
-            let image =
-                image::RgbaImage::from_raw(self.size.width, self.size.height, unpadded_buffer)
-                    .unwrap();
-
+            let unpadded_buffer = buffer_size.unpad(&padded_buffer);
             drop(padded_buffer);
             output_buffer.unmap();
-            image
+            unpadded_buffer
         };
 
-        futures::executor::block_on(future_image)
+        futures::executor::block_on(future_bytes)
+    }
+
+    pub fn read_async(
+        &self,
+        instance: &Instance,
+        pool: &StagingBufferPool,
+        mip_level: u32,
+        origin: Origin3d,
+        extent: Extent3d,
+    ) -> TextureReadback {
+        let buffer_size = self.read_buffer_size(mip_level, origin, extent);
+        let staging_buffer = pool.acquire(instance, buffer_size.byte_count());
+        self.copy_to_buffer(
+            instance,
+            mip_level,
+            origin,
+            extent,
+            staging_buffer.buffer(),
+            &buffer_size,
+        );
+        TextureReadback::new(staging_buffer, buffer_size, pool.clone())
+    }
+
+    fn read_buffer_size(
+        &self,
+        mip_level: u32,
+        origin: Origin3d,
+        extent: Extent3d,
+    ) -> TextureBufferSize {
+        let block = TextureFormatBlock::from(self.format);
+        let mip_level_size = self.mip_level_size(mip_level);
+        assert!(
+            origin.x + extent.width <= mip_level_size.width
+                && origin.y + extent.height <= mip_level_size.height
+                && origin.z + extent.depth <= mip_level_size.depth,
+            "The texture region is out of bounds"
+        );
+        assert!(
+            origin.x % block.width == 0
+                && origin.y % block.height == 0
+                && extent.width % block.width == 0
+                && extent.height % block.height == 0,
+            "The texture region isn't aligned to the format block size"
+        );
+        TextureBufferSize::new(&extent, &block)
+    }
+
+    fn copy_to_buffer(
+        &self,
+        instance: &Instance,
+        mip_level: u32,
+        origin: Origin3d,
+        extent: Extent3d,
+        buffer: &Buffer,
+        buffer_size: &TextureBufferSize,
+    ) {
+        let mut encoder = CommandEncoder::new(instance, &CommandEncoderDescriptor::default());
+        encoder.copy_texture_to_buffer(
+            TextureCopyView {
+                texture: &self.value,
+                mip_level,
+                origin,
+            },
+            BufferCopyView {
+                buffer,
+                layout: TextureDataLayout {
+                    offset: 0,
+                    bytes_per_row: buffer_size.padded_bytes_per_row as u32,
+                    rows_per_image: extent.height,
+                },
+            },
+            extent,
+        );
+        instance.submit(Some(encoder.finish()));
     }
 
     pub fn write(
@@ -576,6 +1276,77 @@ impl Texture {
             size,
         );
     }
+
+    pub fn layer_count(&self) -> u32 {
+        match self.dimension {
+            TextureDimension::D3 => 1,
+            _ => self.size.depth,
+        }
+    }
+
+    pub fn write_layer(
+        &self,
+        instance: &Instance,
+        mip_level: u32,
+        layer: u32,
+        data: &[u8],
+        data_layout: TextureDataLayout,
+    ) {
+        assert!(layer < self.layer_count(), "Invalid texture layer");
+        let mip_level_size = self.mip_level_size(mip_level);
+        self.write(
+            instance,
+            mip_level,
+            Origin3d {
+                x: 0,
+                y: 0,
+                z: layer,
+            },
+            data,
+            data_layout,
+            Extent3d {
+                width: mip_level_size.width,
+                height: mip_level_size.height,
+                depth: 1,
+            },
+        );
+    }
+
+    pub fn create_layer_view(&self, layer: u32) -> TextureView {
+        assert!(layer < self.layer_count(), "Invalid texture layer");
+        self.create_view(&TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::D2),
+            base_array_layer: layer,
+            array_layer_count: NonZeroU32::new(1),
+            ..TextureViewDescriptor::default()
+        })
+    }
+
+    pub fn create_array_view(&self) -> TextureView {
+        assert!(
+            self.dimension == TextureDimension::D2,
+            "Only 2D textures can be viewed as arrays"
+        );
+        self.create_view(&TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::D2Array),
+            array_layer_count: NonZeroU32::new(self.layer_count()),
+            ..TextureViewDescriptor::default()
+        })
+    }
+
+    pub fn create_cube_view(&self) -> TextureView {
+        assert!(
+            self.dimension == TextureDimension::D2
+                && self.size.depth == 6
+                && self.size.width == self.size.height,
+            "The texture isn't a valid cube map"
+        );
+        self.create_view(&TextureViewDescriptor {
+            dimension: Some(TextureViewDimension::Cube),
+            array_layer_count: NonZeroU32::new(6),
+            ..TextureViewDescriptor::default()
+        })
+    }
 }
 
 impl Deref for Texture {
@@ -602,6 +1373,10 @@ impl Sampler {
             value: instance.device.create_sampler(desc),
         }
     }
+
+    pub fn try_new(instance: &Instance, desc: &SamplerDescriptor) -> Result<Self, ValidationError> {
+        instance.create_validated(validate_sampler(desc), || Self::new(instance, desc))
+    }
 }
 
 impl Deref for Sampler {
@@ -646,20 +1421,29 @@ impl DerefMut for SwapChain {
 #[derive(Debug, Clone, PartialEq)]
 pub enum InstanceCreationError {
     AdapterRequestFailed,
+    IncompatibleAdapterSelection,
     FeaturesNotAvailable(Features),
     DeviceRequestFailed(wgpu::RequestDeviceError),
+    TraceDirectoryCreationFailed(std::io::ErrorKind),
 }
 
 impl std::fmt::Display for InstanceCreationError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             InstanceCreationError::AdapterRequestFailed => write!(f, "Adapter request failed"),
+            InstanceCreationError::IncompatibleAdapterSelection => write!(
+                f,
+                "Only the preferred adapter selection can be checked for surface compatibility"
+            ),
             InstanceCreationError::FeaturesNotAvailable(features) => {
                 write!(f, "Required features are not available ({:?})", features)
             }
             InstanceCreationError::DeviceRequestFailed(e) => {
                 write!(f, "Device request failed ({})", e)
             }
+            InstanceCreationError::TraceDirectoryCreationFailed(kind) => {
+                write!(f, "Trace directory creation failed ({:?})", kind)
+            }
         }
     }
 }
@@ -683,11 +1467,15 @@ impl From<wgpu::RequestDeviceError> for InstanceCreationError {
 mod tests {
     use super::*;
 
+    use galvanic_assert::{matchers::*, *};
+
     use rae_app::{
         event::{EventLoop, EventLoopAnyThread},
         window::WindowBuilder,
     };
 
+    use crate::core::{include_spirv, ProgrammableStageDescriptor};
+
     #[test]
     fn default_config() {
         let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
@@ -699,14 +1487,25 @@ mod tests {
         let instance = Instance::new(&InstanceDescriptor {
             backend: Backend::VULKAN,
             power_preference: PowerPreference::Default,
+            adapter_selection: AdapterSelection::Preferred,
+            force_fallback_adapter: false,
             required_features: Features::default(),
             optional_features: Features::empty(),
             required_limits: Limits::default(),
+            trace_path: None,
         })
         .unwrap();
         println!("{:?}", instance.info());
     }
 
+    #[test]
+    fn new_async() {
+        let instance =
+            futures::executor::block_on(Instance::new_async(&InstanceDescriptor::default()))
+                .unwrap();
+        println!("{:?}", instance.info());
+    }
+
     #[test]
     fn new_with_compatible_window() {
         let event_loop = EventLoop::<()>::new_any_thread();
@@ -719,4 +1518,423 @@ mod tests {
         };
         println!("{:?}", instance.info());
     }
+
+    #[test]
+    fn new_with_compatible_window_explicit_adapter_selection() {
+        let event_loop = EventLoop::<()>::new_any_thread();
+        let window = WindowBuilder::new()
+            .with_visible(false)
+            .build(&event_loop)
+            .unwrap();
+        let result = unsafe {
+            Instance::new_with_compatible_window(
+                &InstanceDescriptor {
+                    adapter_selection: AdapterSelection::Index(0),
+                    ..InstanceDescriptor::default()
+                },
+                &window,
+            )
+        };
+        expect_that!(
+            &result.unwrap_err(),
+            eq(InstanceCreationError::IncompatibleAdapterSelection)
+        );
+
+        let result = unsafe {
+            Instance::new_with_compatible_window(
+                &InstanceDescriptor {
+                    force_fallback_adapter: true,
+                    ..InstanceDescriptor::default()
+                },
+                &window,
+            )
+        };
+        expect_that!(
+            &result.unwrap_err(),
+            eq(InstanceCreationError::IncompatibleAdapterSelection)
+        );
+    }
+
+    fn create_rgba_texture(instance: &Instance, width: u32, height: u32) -> Texture {
+        let size = Extent3d {
+            width,
+            height,
+            depth: 1,
+        };
+        let texture = Texture::new(
+            instance,
+            &TextureDescriptor {
+                label: None,
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba8Unorm,
+                usage: TextureUsage::COPY_SRC | TextureUsage::COPY_DST,
+            },
+        );
+        let data: Vec<u8> = (0..(4 * width * height) as u8).collect();
+        texture.write(
+            instance,
+            0,
+            Origin3d::ZERO,
+            &data,
+            TextureDataLayout {
+                offset: 0,
+                bytes_per_row: 4 * width,
+                rows_per_image: 0,
+            },
+            size,
+        );
+        texture
+    }
+
+    #[test]
+    fn texture_format_block() {
+        expect_that!(
+            &TextureFormatBlock::from(TextureFormat::R8Unorm),
+            eq(TextureFormatBlock {
+                byte_count: 1,
+                width: 1,
+                height: 1
+            })
+        );
+        expect_that!(
+            &TextureFormatBlock::from(TextureFormat::Rgba32Float),
+            eq(TextureFormatBlock {
+                byte_count: 16,
+                width: 1,
+                height: 1
+            })
+        );
+        expect_that!(
+            &TextureFormatBlock::from(TextureFormat::Bc1RgbaUnorm),
+            eq(TextureFormatBlock {
+                byte_count: 8,
+                width: 4,
+                height: 4
+            })
+        );
+    }
+
+    #[test]
+    fn texture_read_bytes() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let texture = create_rgba_texture(&instance, 3, 2);
+        let bytes = texture.read_bytes(
+            &instance,
+            0,
+            Origin3d { x: 1, y: 1, z: 0 },
+            Extent3d {
+                width: 2,
+                height: 1,
+                depth: 1,
+            },
+        );
+        expect_that!(&bytes, eq(vec![16, 17, 18, 19, 20, 21, 22, 23]));
+    }
+
+    #[test]
+    fn texture_to_image_region() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let texture = create_rgba_texture(&instance, 3, 2);
+        let image = texture.to_image_region(
+            &instance,
+            0,
+            Origin3d { x: 2, y: 0, z: 0 },
+            Extent3d {
+                width: 1,
+                height: 2,
+                depth: 1,
+            },
+        );
+        expect_that!(&image.dimensions(), eq((1, 2)));
+        expect_that!(&image.get_pixel(0, 0).0, eq([8, 9, 10, 11]));
+        expect_that!(&image.get_pixel(0, 1).0, eq([20, 21, 22, 23]));
+
+        let image = texture.to_image(&instance);
+        expect_that!(&image.dimensions(), eq((3, 2)));
+    }
+
+    #[test]
+    fn texture_write_layer() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let texture = Texture::new(
+            &instance,
+            &TextureDescriptor {
+                label: None,
+                size: Extent3d {
+                    width: 2,
+                    height: 1,
+                    depth: 3,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba8Unorm,
+                usage: TextureUsage::COPY_SRC | TextureUsage::COPY_DST | TextureUsage::SAMPLED,
+            },
+        );
+        expect_that!(&texture.layer_count(), eq(3));
+        texture.write_layer(
+            &instance,
+            0,
+            1,
+            &[1, 2, 3, 4, 5, 6, 7, 8],
+            TextureDataLayout {
+                offset: 0,
+                bytes_per_row: 8,
+                rows_per_image: 1,
+            },
+        );
+        let bytes = texture.read_bytes(
+            &instance,
+            0,
+            Origin3d { x: 0, y: 0, z: 1 },
+            Extent3d {
+                width: 2,
+                height: 1,
+                depth: 1,
+            },
+        );
+        expect_that!(&bytes, eq(vec![1, 2, 3, 4, 5, 6, 7, 8]));
+        let _view = texture.create_array_view();
+        let _view = texture.create_layer_view(2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid texture layer")]
+    fn texture_invalid_layer_view() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let texture = create_rgba_texture(&instance, 2, 2);
+        texture.create_layer_view(1);
+    }
+
+    #[test]
+    #[should_panic(expected = "The texture region is out of bounds")]
+    fn texture_read_bytes_out_of_bounds() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let texture = create_rgba_texture(&instance, 3, 2);
+        texture.read_bytes(
+            &instance,
+            0,
+            Origin3d { x: 2, y: 0, z: 0 },
+            Extent3d {
+                width: 2,
+                height: 1,
+                depth: 1,
+            },
+        );
+    }
+
+    fn conflicting_bind_group_layout_descriptor() -> BindGroupLayoutDescriptor<'static> {
+        const ENTRY: wgpu::BindGroupLayoutEntry = wgpu::BindGroupLayoutEntry {
+            binding: 0,
+            visibility: ShaderStage::VERTEX,
+            ty: wgpu::BindingType::Sampler { comparison: false },
+            count: None,
+        };
+        const ENTRIES: &[wgpu::BindGroupLayoutEntry] = &[ENTRY, ENTRY];
+        BindGroupLayoutDescriptor {
+            label: None,
+            entries: ENTRIES,
+        }
+    }
+
+    #[test]
+    fn trace() {
+        let trace_path = std::env::temp_dir().join("rae_gfx_instance_trace");
+        let _ = std::fs::remove_dir_all(&trace_path);
+        let instance = Instance::new(&InstanceDescriptor {
+            trace_path: Some(trace_path.clone()),
+            ..InstanceDescriptor::default()
+        })
+        .unwrap();
+        let _texture = create_rgba_texture(&instance, 2, 2);
+        expect_that!(trace_path.join("trace.ron").is_file());
+    }
+
+    #[test]
+    fn adapter_selection() {
+        let adapters = Instance::enumerate_adapters(Backend::PRIMARY);
+        expect_that!(!adapters.is_empty());
+
+        let instance = Instance::new(&InstanceDescriptor {
+            adapter_selection: AdapterSelection::Index(0),
+            ..InstanceDescriptor::default()
+        })
+        .unwrap();
+        expect_that!(&instance.info(), eq(adapters[0].info.clone()));
+
+        let instance = Instance::new(&InstanceDescriptor {
+            adapter_selection: AdapterSelection::DeviceType(adapters[0].info.device_type.clone()),
+            ..InstanceDescriptor::default()
+        })
+        .unwrap();
+        expect_that!(
+            &instance.info().device_type,
+            eq(adapters[0].info.device_type.clone())
+        );
+
+        let instance = Instance::new(&InstanceDescriptor {
+            adapter_selection: AdapterSelection::Index(adapters.len()),
+            ..InstanceDescriptor::default()
+        });
+        expect_that!(
+            &instance.unwrap_err(),
+            eq(InstanceCreationError::AdapterRequestFailed)
+        );
+    }
+
+    #[test]
+    fn fallback_adapter() {
+        let has_cpu_adapter = Instance::enumerate_adapters(Backend::PRIMARY)
+            .iter()
+            .any(|x| x.info.device_type == DeviceType::Cpu);
+        let instance = Instance::new(&InstanceDescriptor {
+            force_fallback_adapter: true,
+            ..InstanceDescriptor::default()
+        });
+        if has_cpu_adapter {
+            expect_that!(&instance.unwrap().info().device_type, eq(DeviceType::Cpu));
+        } else {
+            expect_that!(
+                &instance.unwrap_err(),
+                eq(InstanceCreationError::AdapterRequestFailed)
+            );
+        }
+    }
+
+    #[test]
+    fn recreate() {
+        let mut instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        expect_that!(&instance.generation(), eq(0));
+        expect_that!(!instance.is_device_lost());
+
+        instance.mark_device_lost();
+        expect_that!(instance.is_device_lost());
+        instance.recreate(None).unwrap();
+        expect_that!(&instance.generation(), eq(1));
+        expect_that!(!instance.is_device_lost());
+
+        futures::executor::block_on(instance.recreate_async(None)).unwrap();
+        expect_that!(&instance.generation(), eq(2));
+
+        let texture = create_rgba_texture(&instance, 2, 2);
+        expect_that!(
+            &texture
+                .read_bytes(&instance, 0, Origin3d::ZERO, *texture.size())
+                .len(),
+            eq(16)
+        );
+    }
+
+    #[test]
+    fn blit_pipeline_cache() {
+        let mut instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let pipeline = instance.blit_pipeline(TextureFormat::Rgba8Unorm, 1, FilterMode::Linear);
+        expect_that!(
+            &Arc::ptr_eq(
+                &pipeline,
+                &instance.blit_pipeline(TextureFormat::Rgba8Unorm, 1, FilterMode::Linear)
+            ),
+            eq(true)
+        );
+        expect_that!(
+            &Arc::ptr_eq(
+                &pipeline,
+                &instance.blit_pipeline(TextureFormat::Rgba8Unorm, 1, FilterMode::Nearest)
+            ),
+            eq(false)
+        );
+
+        instance.recreate(None).unwrap();
+        expect_that!(
+            &Arc::ptr_eq(
+                &pipeline,
+                &instance.blit_pipeline(TextureFormat::Rgba8Unorm, 1, FilterMode::Linear)
+            ),
+            eq(false)
+        );
+    }
+
+    #[test]
+    fn try_new_validation_error() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let bind_group_layout =
+            BindGroupLayout::try_new(&instance, &conflicting_bind_group_layout_descriptor());
+        expect_that!(bind_group_layout.is_err());
+
+        let sampler = Sampler::try_new(&instance, &SamplerDescriptor::default());
+        expect_that!(sampler.is_ok());
+
+        let shader_module = ShaderModule::try_new(
+            &instance,
+            ShaderModuleSource::SpirV((&[0u32; 4][..]).into()),
+        );
+        expect_that!(shader_module.is_err());
+
+        let buffer = Buffer::try_new(
+            &instance,
+            &BufferDescriptor {
+                label: None,
+                size: 16,
+                usage: BufferUsage::MAP_READ | BufferUsage::UNIFORM,
+                mapped_at_creation: false,
+            },
+        );
+        expect_that!(buffer.is_err());
+
+        let texture = Texture::try_new(
+            &instance,
+            &TextureDescriptor {
+                label: None,
+                size: Extent3d {
+                    width: 4,
+                    height: 4,
+                    depth: 1,
+                },
+                mip_level_count: 4,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba8Unorm,
+                usage: TextureUsage::SAMPLED,
+            },
+        );
+        expect_that!(texture.is_err());
+
+        let module = ShaderModule::new(
+            &instance,
+            include_spirv!("shaders/gen/spirv/double_values.comp.spv"),
+        );
+        let compute_pipeline = ComputePipeline::try_new(
+            &instance,
+            &ComputePipelineDescriptor {
+                label: None,
+                layout: None,
+                compute_stage: ProgrammableStageDescriptor {
+                    module: &module,
+                    entry_point: "",
+                },
+            },
+        );
+        expect_that!(compute_pipeline.is_err());
+    }
+
+    #[test]
+    fn error_scope() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        instance.push_error_scope();
+        expect_that!(&instance.pop_error_scope(), eq(Some(Ok(()))));
+
+        instance.push_error_scope();
+        instance.push_error_scope();
+        let error =
+            BindGroupLayout::try_new(&instance, &conflicting_bind_group_layout_descriptor())
+                .unwrap_err();
+        let _sampler = Sampler::try_new(&instance, &SamplerDescriptor::default()).unwrap();
+        expect_that!(&instance.pop_error_scope(), eq(Some(Err(error))));
+        expect_that!(&instance.pop_error_scope(), eq(Some(Ok(()))));
+        expect_that!(&instance.pop_error_scope(), eq(None));
+    }
 }
@@ -0,0 +1,159 @@
+use std::num::NonZeroU32;
+
+use super::{
+    CommandEncoder, CommandEncoderDescriptor, Extent3d, FilterMode, Instance, LoadOp, Operations,
+    RenderPassColorAttachmentDescriptor, RenderPassDescriptor, Texture, TextureDimension,
+    TextureUsage, TextureViewDescriptor, TextureViewDimension,
+};
+
+pub fn full_mip_level_count(size: &Extent3d) -> u32 {
+    32 - std::cmp::max(1, std::cmp::max(size.width, size.height)).leading_zeros()
+}
+
+impl Texture {
+    // Each mip level is rendered by sampling the previous one, for every
+    // array layer of the texture.
+    pub fn generate_mipmaps(&self, instance: &Instance, filter: FilterMode) {
+        assert!(
+            self.dimension() == TextureDimension::D2,
+            "Mipmaps can only be generated for 2D textures"
+        );
+        assert!(
+            self.usage()
+                .contains(TextureUsage::SAMPLED | TextureUsage::OUTPUT_ATTACHMENT),
+            "Mipmap generation requires the SAMPLED and OUTPUT_ATTACHMENT texture usages"
+        );
+
+        let blit_pipeline = instance.blit_pipeline(self.format(), 1, filter);
+
+        let mut encoder = CommandEncoder::new(
+            instance,
+            &CommandEncoderDescriptor {
+                label: Some("mipmap generation"),
+            },
+        );
+        for layer in 0..self.size().depth {
+            let level_view = |mip_level| {
+                self.create_view(&TextureViewDescriptor {
+                    dimension: Some(TextureViewDimension::D2),
+                    base_mip_level: mip_level,
+                    level_count: NonZeroU32::new(1),
+                    base_array_layer: layer,
+                    array_layer_count: NonZeroU32::new(1),
+                    ..TextureViewDescriptor::default()
+                })
+            };
+            for mip_level in 1..self.mip_level_count() {
+                let source_view = level_view(mip_level - 1);
+                let target_view = level_view(mip_level);
+                let bind_group = blit_pipeline.bind_group(instance, &source_view);
+                let mut pass = encoder.begin_render_pass(&RenderPassDescriptor {
+                    color_attachments: &[RenderPassColorAttachmentDescriptor {
+                        attachment: &target_view,
+                        resolve_target: None,
+                        ops: Operations {
+                            load: LoadOp::Load,
+                            store: true,
+                        },
+                    }],
+                    depth_stencil_attachment: None,
+                });
+                pass.push_debug_group(&format!("layer {} mip level {}", layer, mip_level));
+                blit_pipeline.draw(&mut pass, &bind_group);
+                pass.pop_debug_group();
+            }
+        }
+        instance.submit(Some(encoder.finish()));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use galvanic_assert::{matchers::*, *};
+
+    use crate::core::{ColorSpace, InstanceDescriptor, Origin3d, TextureFormat};
+
+    #[test]
+    fn mip_level_count() {
+        expect_that!(
+            &full_mip_level_count(&Extent3d {
+                width: 1,
+                height: 1,
+                depth: 1
+            }),
+            eq(1)
+        );
+        expect_that!(
+            &full_mip_level_count(&Extent3d {
+                width: 256,
+                height: 17,
+                depth: 1
+            }),
+            eq(9)
+        );
+        expect_that!(
+            &full_mip_level_count(&Extent3d {
+                width: 3,
+                height: 5,
+                depth: 1
+            }),
+            eq(3)
+        );
+    }
+
+    #[test]
+    fn from_image_with_mipmaps() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let img = image::RgbaImage::from_fn(8, 4, |x, _| {
+            if x % 2 == 0 {
+                image::Rgba([0, 0, 0, 255])
+            } else {
+                image::Rgba([255, 255, 255, 255])
+            }
+        });
+        let texture = Texture::from_image_with_mipmaps(
+            &instance,
+            None,
+            &image::DynamicImage::ImageRgba8(img),
+            ColorSpace::Linear,
+            TextureUsage::COPY_SRC,
+            FilterMode::Linear,
+        );
+        expect_that!(&texture.format(), eq(TextureFormat::Rgba8Unorm));
+        expect_that!(&texture.mip_level_count(), eq(4));
+
+        let last_level = texture.mip_level_count() - 1;
+        let size = texture.mip_level_size(last_level);
+        expect_that!(
+            &size,
+            eq(Extent3d {
+                width: 1,
+                height: 1,
+                depth: 1
+            })
+        );
+        let img = texture.to_image_region(&instance, last_level, Origin3d::ZERO, size);
+        let pixel = img.get_pixel(0, 0).0;
+        expect_that!(&pixel[0], geq(100));
+        expect_that!(&pixel[0], leq(155));
+        expect_that!(&pixel[3], eq(255));
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "Mipmap generation requires the SAMPLED and OUTPUT_ATTACHMENT texture usages"
+    )]
+    fn generate_mipmaps_invalid_usage() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let texture = Texture::from_image(
+            &instance,
+            None,
+            &image::DynamicImage::ImageRgba8(image::RgbaImage::new(4, 4)),
+            ColorSpace::Linear,
+            TextureUsage::SAMPLED,
+        );
+        texture.generate_mipmaps(&instance, FilterMode::Linear);
+    }
+}
@@ -2,19 +2,20 @@ pub use wgpu::{
     include_spirv, util::BufferInitDescriptor, AdapterInfo, AddressMode, BackendBit as Backend,
     BindGroupDescriptor, BindGroupEntry, BindGroupLayoutDescriptor, BindGroupLayoutEntry,
     BindingResource, BindingType, BlendDescriptor, BlendFactor, BlendOperation, BufferAddress,
-    BufferCopyView, BufferDescriptor, BufferSlice, BufferUsage, ColorStateDescriptor, ColorWrite,
-    CommandBuffer, CommandEncoderDescriptor, CompareFunction, CullMode,
-    DepthStencilStateDescriptor, Extent3d, Features, FilterMode, FrontFace, IndexFormat,
-    InputStepMode, Limits, LoadOp, Maintain, MapMode, Operations, Origin3d,
-    PipelineLayoutDescriptor, PowerPreference, PresentMode, PrimitiveTopology,
-    ProgrammableStageDescriptor, PushConstantRange, RasterizationStateDescriptor,
-    RenderBundleEncoderDescriptor, RenderPass, RenderPassColorAttachmentDescriptor,
-    RenderPassDepthStencilAttachmentDescriptor, RenderPassDescriptor, RenderPipelineDescriptor,
-    SamplerDescriptor, ShaderModuleSource, ShaderStage, StencilStateDescriptor,
-    SwapChainDescriptor, SwapChainError, SwapChainFrame, SwapChainTexture, TextureComponentType,
-    TextureCopyView, TextureDataLayout, TextureDescriptor, TextureDimension, TextureFormat,
-    TextureUsage, TextureView, TextureViewDescriptor, TextureViewDimension,
-    VertexAttributeDescriptor, VertexBufferDescriptor, VertexFormat, VertexStateDescriptor,
+    BufferAsyncError, BufferCopyView, BufferDescriptor, BufferSlice, BufferUsage,
+    ColorStateDescriptor, ColorWrite, CommandBuffer, CommandEncoderDescriptor, CompareFunction,
+    ComputePipelineDescriptor, CullMode, DepthStencilStateDescriptor, DeviceType, DynamicOffset,
+    Extent3d, Features, FilterMode, FrontFace, IndexFormat, InputStepMode, Limits, LoadOp,
+    Maintain, MapMode, Operations, Origin3d, PipelineLayoutDescriptor, PowerPreference,
+    PresentMode, PrimitiveTopology, ProgrammableStageDescriptor, PushConstantRange,
+    RasterizationStateDescriptor, RenderBundleDescriptor, RenderBundleEncoderDescriptor,
+    RenderPass, RenderPassColorAttachmentDescriptor, RenderPassDepthStencilAttachmentDescriptor,
+    RenderPassDescriptor, RenderPipelineDescriptor, SamplerDescriptor, ShaderModuleSource,
+    ShaderStage, StencilStateDescriptor, SwapChainDescriptor, SwapChainError, SwapChainFrame,
+    SwapChainTexture, TextureComponentType, TextureCopyView, TextureDataLayout, TextureDescriptor,
+    TextureDimension, TextureFormat, TextureUsage, TextureView, TextureViewDescriptor,
+    TextureViewDimension, VertexAttributeDescriptor, VertexBufferDescriptor, VertexFormat,
+    VertexStateDescriptor, BIND_BUFFER_ALIGNMENT,
 };
 
 mod size;
@@ -23,6 +24,9 @@ pub use size::*;
 mod color;
 pub use color::*;
 
+mod error_scope;
+pub use error_scope::*;
+
 mod instance;
 pub use instance::*;
 
@@ -35,8 +39,37 @@ pub use canvas_window::*;
 mod canvas_texture;
 pub use canvas_texture::*;
 
+mod gpu_profiler;
+pub use gpu_profiler::*;
+
 mod command_sequence;
 pub use command_sequence::*;
 
 mod mesh;
 pub use mesh::*;
+
+mod uniform_buffer;
+pub use uniform_buffer::*;
+
+mod texture_readback;
+pub use texture_readback::*;
+
+mod texture_image;
+pub use texture_image::*;
+
+mod blit;
+pub(crate) use blit::*;
+
+mod mipmap;
+pub use mipmap::*;
+
+mod texture_container;
+pub use texture_container::*;
+
+mod validation;
+pub(crate) use validation::*;
+
+#[cfg(feature = "replay")]
+mod trace_replay;
+#[cfg(feature = "replay")]
+pub use trace_replay::*;
@@ -0,0 +1,10 @@
+#version 450
+#extension GL_ARB_separate_shader_objects : enable
+
+layout(location = 0) out vec2 outTexCoords;
+
+void main() {
+    vec2 texCoords = vec2((gl_VertexIndex << 1) & 2, gl_VertexIndex & 2);
+    outTexCoords = texCoords;
+    gl_Position = vec4(texCoords * vec2(2.0, -2.0) + vec2(-1.0, 1.0), 0.0, 1.0);
+}
@@ -0,0 +1,589 @@
+use std::path::Path;
+
+use super::{
+    Extent3d, Features, Instance, Origin3d, Texture, TextureDataLayout, TextureDescriptor,
+    TextureDimension, TextureFormat, TextureFormatBlock, TextureUsage,
+};
+
+#[derive(Debug)]
+pub enum TextureLoadError {
+    ReadFailed(std::io::Error),
+    DdsParsingFailed(ddsfile::Error),
+    Ktx2ParsingFailed(ktx2::ParseError),
+    UnsupportedFormat(String),
+    UnsupportedLayout(String),
+    FeaturesNotAvailable(Features),
+    DataSizeMismatch,
+}
+
+impl std::fmt::Display for TextureLoadError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TextureLoadError::ReadFailed(e) => write!(f, "Failed to read texture file ({})", e),
+            TextureLoadError::DdsParsingFailed(e) => {
+                write!(f, "Failed to parse DDS texture ({})", e)
+            }
+            TextureLoadError::Ktx2ParsingFailed(e) => {
+                write!(f, "Failed to parse KTX2 texture ({})", e)
+            }
+            TextureLoadError::UnsupportedFormat(format) => {
+                write!(f, "Unsupported texture format ({})", format)
+            }
+            TextureLoadError::UnsupportedLayout(layout) => {
+                write!(f, "Unsupported texture layout ({})", layout)
+            }
+            TextureLoadError::FeaturesNotAvailable(features) => write!(
+                f,
+                "Features required by the texture format are not available ({:?})",
+                features
+            ),
+            TextureLoadError::DataSizeMismatch => {
+                write!(f, "The texture data doesn't match the texture size")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TextureLoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TextureLoadError::ReadFailed(e) => Some(e),
+            TextureLoadError::DdsParsingFailed(e) => Some(e),
+            TextureLoadError::Ktx2ParsingFailed(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for TextureLoadError {
+    fn from(e: std::io::Error) -> Self {
+        TextureLoadError::ReadFailed(e)
+    }
+}
+
+impl From<ddsfile::Error> for TextureLoadError {
+    fn from(e: ddsfile::Error) -> Self {
+        TextureLoadError::DdsParsingFailed(e)
+    }
+}
+
+impl From<ktx2::ParseError> for TextureLoadError {
+    fn from(e: ktx2::ParseError) -> Self {
+        TextureLoadError::Ktx2ParsingFailed(e)
+    }
+}
+
+pub fn texture_format_features(format: TextureFormat) -> Features {
+    match format {
+        TextureFormat::Bc1RgbaUnorm
+        | TextureFormat::Bc1RgbaUnormSrgb
+        | TextureFormat::Bc2RgbaUnorm
+        | TextureFormat::Bc2RgbaUnormSrgb
+        | TextureFormat::Bc3RgbaUnorm
+        | TextureFormat::Bc3RgbaUnormSrgb
+        | TextureFormat::Bc4RUnorm
+        | TextureFormat::Bc4RSnorm
+        | TextureFormat::Bc5RgUnorm
+        | TextureFormat::Bc5RgSnorm
+        | TextureFormat::Bc6hRgbUfloat
+        | TextureFormat::Bc6hRgbSfloat
+        | TextureFormat::Bc7RgbaUnorm
+        | TextureFormat::Bc7RgbaUnormSrgb => Features::TEXTURE_COMPRESSION_BC,
+        _ => Features::default(),
+    }
+}
+
+const KTX2_FORMATS: [(ktx2::Format, TextureFormat); 8] = [
+    (ktx2::Format::R8G8B8A8_UNORM, TextureFormat::Rgba8Unorm),
+    (ktx2::Format::R8G8B8A8_SRGB, TextureFormat::Rgba8UnormSrgb),
+    (
+        ktx2::Format::BC1_RGBA_UNORM_BLOCK,
+        TextureFormat::Bc1RgbaUnorm,
+    ),
+    (
+        ktx2::Format::BC1_RGBA_SRGB_BLOCK,
+        TextureFormat::Bc1RgbaUnormSrgb,
+    ),
+    (ktx2::Format::BC3_UNORM_BLOCK, TextureFormat::Bc3RgbaUnorm),
+    (
+        ktx2::Format::BC3_SRGB_BLOCK,
+        TextureFormat::Bc3RgbaUnormSrgb,
+    ),
+    (ktx2::Format::BC7_UNORM_BLOCK, TextureFormat::Bc7RgbaUnorm),
+    (
+        ktx2::Format::BC7_SRGB_BLOCK,
+        TextureFormat::Bc7RgbaUnormSrgb,
+    ),
+];
+
+fn dds_texture_format(dds: &ddsfile::Dds) -> Result<TextureFormat, TextureLoadError> {
+    if let Some(format) = dds.get_dxgi_format() {
+        return match format {
+            ddsfile::DxgiFormat::R8G8B8A8_UNorm => Ok(TextureFormat::Rgba8Unorm),
+            ddsfile::DxgiFormat::R8G8B8A8_UNorm_sRGB => Ok(TextureFormat::Rgba8UnormSrgb),
+            ddsfile::DxgiFormat::BC1_UNorm => Ok(TextureFormat::Bc1RgbaUnorm),
+            ddsfile::DxgiFormat::BC1_UNorm_sRGB => Ok(TextureFormat::Bc1RgbaUnormSrgb),
+            ddsfile::DxgiFormat::BC3_UNorm => Ok(TextureFormat::Bc3RgbaUnorm),
+            ddsfile::DxgiFormat::BC3_UNorm_sRGB => Ok(TextureFormat::Bc3RgbaUnormSrgb),
+            ddsfile::DxgiFormat::BC7_UNorm => Ok(TextureFormat::Bc7RgbaUnorm),
+            ddsfile::DxgiFormat::BC7_UNorm_sRGB => Ok(TextureFormat::Bc7RgbaUnormSrgb),
+            _ => Err(TextureLoadError::UnsupportedFormat(format!("{:?}", format))),
+        };
+    }
+    match dds.get_d3d_format() {
+        Some(ddsfile::D3DFormat::A8B8G8R8) => Ok(TextureFormat::Rgba8Unorm),
+        Some(ddsfile::D3DFormat::DXT1) => Ok(TextureFormat::Bc1RgbaUnorm),
+        Some(ddsfile::D3DFormat::DXT5) => Ok(TextureFormat::Bc3RgbaUnorm),
+        Some(format) => Err(TextureLoadError::UnsupportedFormat(format!("{:?}", format))),
+        None => Err(TextureLoadError::UnsupportedFormat(String::from("Unknown"))),
+    }
+}
+
+#[derive(Debug)]
+struct TextureLevelData<'a> {
+    mip_level: u32,
+    base_layer: u32,
+    layer_count: u32,
+    data: &'a [u8],
+}
+
+fn mip_level_extent(size: &Extent3d, mip_level: u32) -> Extent3d {
+    Extent3d {
+        width: std::cmp::max(1, size.width >> mip_level),
+        height: std::cmp::max(1, size.height >> mip_level),
+        depth: 1,
+    }
+}
+
+fn mip_level_byte_count(size: &Extent3d, block: &TextureFormatBlock) -> (u32, u32, usize) {
+    let bytes_per_row = (size.width + block.width - 1) / block.width * block.byte_count;
+    let rows_per_image = (size.height + block.height - 1) / block.height * block.height;
+    let byte_count = bytes_per_row as usize * (rows_per_image / block.height) as usize;
+    (bytes_per_row, rows_per_image, byte_count)
+}
+
+impl Texture {
+    pub fn from_dds<R: std::io::Read>(
+        instance: &Instance,
+        label: Option<&str>,
+        reader: R,
+        usage: TextureUsage,
+    ) -> Result<Self, TextureLoadError> {
+        let dds = ddsfile::Dds::read(reader)?;
+        let format = dds_texture_format(&dds)?;
+        if dds.get_depth() > 1 {
+            return Err(TextureLoadError::UnsupportedLayout(String::from(
+                "3D textures",
+            )));
+        }
+
+        // Cubemaps are loaded as 6 layers per cube. The layer count of legacy
+        // cubemaps already includes the faces, as they can't be arrays.
+        let caps2 = dds.header.caps2;
+        if caps2.contains(ddsfile::Caps2::CUBEMAP)
+            && !caps2.contains(ddsfile::Caps2::CUBEMAP_ALLFACES)
+        {
+            return Err(TextureLoadError::UnsupportedLayout(String::from(
+                "Partial cubemaps",
+            )));
+        }
+        let cube_face_count = match &dds.header10 {
+            Some(header10) if header10.misc_flag.contains(ddsfile::MiscFlag::TEXTURECUBE) => 6,
+            _ => 1,
+        };
+        let size = Extent3d {
+            width: dds.get_width(),
+            height: dds.get_height(),
+            depth: dds.get_num_array_layers() * cube_face_count,
+        };
+        let mip_level_count = std::cmp::max(1, dds.get_num_mipmap_levels());
+
+        // DDS files store all mip levels of a layer contiguously.
+        let block = TextureFormatBlock::from(format);
+        let mut levels = Vec::new();
+        let mut offset = 0;
+        for layer in 0..size.depth {
+            for mip_level in 0..mip_level_count {
+                let (_, _, byte_count) =
+                    mip_level_byte_count(&mip_level_extent(&size, mip_level), &block);
+                let data = dds
+                    .data
+                    .get(offset..offset + byte_count)
+                    .ok_or(TextureLoadError::DataSizeMismatch)?;
+                levels.push(TextureLevelData {
+                    mip_level,
+                    base_layer: layer,
+                    layer_count: 1,
+                    data,
+                });
+                offset += byte_count;
+            }
+        }
+        Self::from_level_data(
+            instance,
+            label,
+            format,
+            size,
+            mip_level_count,
+            usage,
+            &levels,
+        )
+    }
+
+    pub fn from_dds_file<P: AsRef<Path>>(
+        instance: &Instance,
+        label: Option<&str>,
+        path: P,
+        usage: TextureUsage,
+    ) -> Result<Self, TextureLoadError> {
+        let file = std::fs::File::open(path)?;
+        Self::from_dds(instance, label, std::io::BufReader::new(file), usage)
+    }
+
+    pub fn from_ktx2(
+        instance: &Instance,
+        label: Option<&str>,
+        bytes: &[u8],
+        usage: TextureUsage,
+    ) -> Result<Self, TextureLoadError> {
+        let reader = ktx2::Reader::new(bytes)?;
+        let header = reader.header();
+        let format = match KTX2_FORMATS.iter().find(|(f, _)| Some(*f) == header.format) {
+            Some((_, format)) => *format,
+            None => {
+                return Err(TextureLoadError::UnsupportedFormat(format!(
+                    "{:?}",
+                    header.format
+                )))
+            }
+        };
+        if header.supercompression_scheme.is_some() {
+            return Err(TextureLoadError::UnsupportedFormat(format!(
+                "{:?}",
+                header.supercompression_scheme
+            )));
+        }
+        if header.pixel_depth > 1 {
+            return Err(TextureLoadError::UnsupportedLayout(String::from(
+                "3D textures",
+            )));
+        }
+
+        let size = Extent3d {
+            width: header.pixel_width,
+            height: std::cmp::max(1, header.pixel_height),
+            depth: std::cmp::max(1, header.layer_count) * header.face_count,
+        };
+
+        // KTX2 files store all layers and faces of a mip level contiguously.
+        let levels: Vec<TextureLevelData> = reader
+            .levels()
+            .enumerate()
+            .map(|(mip_level, data)| TextureLevelData {
+                mip_level: mip_level as u32,
+                base_layer: 0,
+                layer_count: size.depth,
+                data,
+            })
+            .collect();
+        Self::from_level_data(
+            instance,
+            label,
+            format,
+            size,
+            levels.len() as u32,
+            usage,
+            &levels,
+        )
+    }
+
+    pub fn from_ktx2_file<P: AsRef<Path>>(
+        instance: &Instance,
+        label: Option<&str>,
+        path: P,
+        usage: TextureUsage,
+    ) -> Result<Self, TextureLoadError> {
+        let bytes = std::fs::read(path)?;
+        Self::from_ktx2(instance, label, &bytes, usage)
+    }
+
+    fn from_level_data(
+        instance: &Instance,
+        label: Option<&str>,
+        format: TextureFormat,
+        size: Extent3d,
+        mip_level_count: u32,
+        usage: TextureUsage,
+        levels: &[TextureLevelData],
+    ) -> Result<Self, TextureLoadError> {
+        // Copies must cover whole blocks, the trailing mip levels not made of
+        // whole blocks can't be uploaded and are therefore dropped.
+        let block = TextureFormatBlock::from(format);
+        let mip_level_count = (0..mip_level_count)
+            .take_while(|mip_level| {
+                let extent = mip_level_extent(&size, *mip_level);
+                extent.width % block.width == 0 && extent.height % block.height == 0
+            })
+            .count() as u32;
+        if mip_level_count == 0 {
+            return Err(TextureLoadError::UnsupportedLayout(format!(
+                "The texture isn't made of whole {}x{} blocks",
+                block.width, block.height
+            )));
+        }
+
+        let required_features = texture_format_features(format);
+        if !instance.features().contains(required_features) {
+            return Err(TextureLoadError::FeaturesNotAvailable(
+                required_features - instance.features(),
+            ));
+        }
+
+        let texture = Self::new(
+            instance,
+            &TextureDescriptor {
+                label,
+                size,
+                mip_level_count,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format,
+                usage: usage | TextureUsage::COPY_DST,
+            },
+        );
+        for level in levels.iter().filter(|l| l.mip_level < mip_level_count) {
+            let extent = mip_level_extent(&size, level.mip_level);
+            let (bytes_per_row, rows_per_image, byte_count) = mip_level_byte_count(&extent, &block);
+            if level.data.len() < byte_count * level.layer_count as usize {
+                return Err(TextureLoadError::DataSizeMismatch);
+            }
+            texture.write(
+                instance,
+                level.mip_level,
+                Origin3d {
+                    x: 0,
+                    y: 0,
+                    z: level.base_layer,
+                },
+                level.data,
+                TextureDataLayout {
+                    offset: 0,
+                    bytes_per_row,
+                    rows_per_image,
+                },
+                Extent3d {
+                    width: extent.width,
+                    height: extent.height,
+                    depth: level.layer_count,
+                },
+            );
+        }
+        Ok(texture)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use galvanic_assert::{matchers::*, *};
+
+    use crate::core::InstanceDescriptor;
+
+    fn ktx2_bytes(format: u32, width: u32, height: u32, levels: &[Vec<u8>]) -> Vec<u8> {
+        let header_size = 80 + 24 * levels.len();
+        let mut bytes = vec![
+            0xab, 0x4b, 0x54, 0x58, 0x20, 0x32, 0x30, 0xbb, 0x0d, 0x0a, 0x1a, 0x0a,
+        ];
+        for v in &[format, 1, width, height, 0, 0, 1, levels.len() as u32, 0] {
+            bytes.extend_from_slice(&v.to_le_bytes());
+        }
+        bytes.resize(80, 0);
+        let mut offset = header_size as u64;
+        for level in levels {
+            bytes.extend_from_slice(&offset.to_le_bytes());
+            bytes.extend_from_slice(&(level.len() as u64).to_le_bytes());
+            bytes.extend_from_slice(&(level.len() as u64).to_le_bytes());
+            offset += level.len() as u64;
+        }
+        for level in levels {
+            bytes.extend_from_slice(level);
+        }
+        bytes
+    }
+
+    #[test]
+    fn format_features() {
+        expect_that!(
+            &texture_format_features(TextureFormat::Bc7RgbaUnormSrgb),
+            eq(Features::TEXTURE_COMPRESSION_BC)
+        );
+        expect_that!(
+            &texture_format_features(TextureFormat::Rgba8Unorm),
+            eq(Features::default())
+        );
+    }
+
+    #[test]
+    fn load_dds() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let mut dds = ddsfile::Dds::new_dxgi(ddsfile::NewDxgiParams {
+            height: 2,
+            width: 4,
+            depth: None,
+            format: ddsfile::DxgiFormat::R8G8B8A8_UNorm,
+            mipmap_levels: Some(3),
+            array_layers: None,
+            caps2: None,
+            is_cubemap: false,
+            resource_dimension: ddsfile::D3D10ResourceDimension::Texture2D,
+            alpha_mode: ddsfile::AlphaMode::Straight,
+        })
+        .unwrap();
+        for (i, v) in dds.data.iter_mut().enumerate() {
+            *v = i as u8;
+        }
+        let mut bytes = Vec::new();
+        dds.write(&mut bytes).unwrap();
+
+        let texture =
+            Texture::from_dds(&instance, None, bytes.as_slice(), TextureUsage::COPY_SRC).unwrap();
+        expect_that!(&texture.format(), eq(TextureFormat::Rgba8Unorm));
+        expect_that!(&texture.mip_level_count(), eq(3));
+        expect_that!(
+            &texture.read_bytes(&instance, 0, Origin3d::ZERO, texture.mip_level_size(0)),
+            eq((0..32).collect::<Vec<u8>>())
+        );
+        expect_that!(
+            &texture.read_bytes(&instance, 1, Origin3d::ZERO, texture.mip_level_size(1)),
+            eq((32..40).collect::<Vec<u8>>())
+        );
+    }
+
+    fn legacy_cubemap_dds_bytes(faces: ddsfile::Caps2) -> Vec<u8> {
+        let mut dds = ddsfile::Dds::new_d3d(ddsfile::NewD3dParams {
+            height: 1,
+            width: 1,
+            depth: None,
+            format: ddsfile::D3DFormat::A8B8G8R8,
+            mipmap_levels: None,
+            caps2: Some(ddsfile::Caps2::CUBEMAP | faces),
+        })
+        .unwrap();
+        dds.data = (0..24).collect();
+        let mut bytes = Vec::new();
+        dds.write(&mut bytes).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn load_dds_legacy_cubemap() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let bytes = legacy_cubemap_dds_bytes(ddsfile::Caps2::CUBEMAP_ALLFACES);
+        let texture =
+            Texture::from_dds(&instance, None, bytes.as_slice(), TextureUsage::COPY_SRC).unwrap();
+        expect_that!(&texture.size().depth, eq(6));
+        expect_that!(
+            &texture.read_bytes(&instance, 0, Origin3d::ZERO, *texture.size()),
+            eq((0..24).collect::<Vec<u8>>())
+        );
+    }
+
+    #[test]
+    fn load_dds_partial_cubemap() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let bytes = legacy_cubemap_dds_bytes(ddsfile::Caps2::CUBEMAP_POSITIVEX);
+        match Texture::from_dds(&instance, None, bytes.as_slice(), TextureUsage::COPY_SRC) {
+            Err(TextureLoadError::UnsupportedLayout(_)) => (),
+            _ => panic!("Unexpected result"),
+        }
+    }
+
+    #[test]
+    fn load_ktx2() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let bytes = ktx2_bytes(37, 2, 1, &[(0..8).collect(), (8..12).collect()]);
+        let texture = Texture::from_ktx2(&instance, None, &bytes, TextureUsage::COPY_SRC).unwrap();
+        expect_that!(&texture.format(), eq(TextureFormat::Rgba8Unorm));
+        expect_that!(&texture.mip_level_count(), eq(2));
+        expect_that!(
+            &texture.read_bytes(&instance, 1, Origin3d::ZERO, texture.mip_level_size(1)),
+            eq((8..12).collect::<Vec<u8>>())
+        );
+    }
+
+    #[test]
+    fn load_ktx2_bc7() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let bytes = ktx2_bytes(145, 8, 4, &[vec![0; 32]]);
+        let result = Texture::from_ktx2(&instance, None, &bytes, TextureUsage::SAMPLED);
+        if instance
+            .features()
+            .contains(Features::TEXTURE_COMPRESSION_BC)
+        {
+            let texture = result.unwrap();
+            expect_that!(&texture.format(), eq(TextureFormat::Bc7RgbaUnorm));
+            expect_that!(&texture.mip_level_count(), eq(1));
+        } else {
+            match result {
+                Err(TextureLoadError::FeaturesNotAvailable(features)) => {
+                    expect_that!(&features, eq(Features::TEXTURE_COMPRESSION_BC))
+                }
+                _ => panic!("Unexpected result"),
+            }
+        }
+    }
+
+    #[test]
+    fn load_ktx2_partial_block_mip_level() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        // The 4x2 mip level doesn't cover a whole block and is dropped.
+        let bytes = ktx2_bytes(145, 8, 4, &[vec![0; 32], vec![0; 16]]);
+        let result = Texture::from_ktx2(&instance, None, &bytes, TextureUsage::SAMPLED);
+        if instance
+            .features()
+            .contains(Features::TEXTURE_COMPRESSION_BC)
+        {
+            let texture = result.unwrap();
+            expect_that!(&texture.format(), eq(TextureFormat::Bc7RgbaUnorm));
+            expect_that!(&texture.mip_level_count(), eq(1));
+        } else {
+            match result {
+                Err(TextureLoadError::FeaturesNotAvailable(features)) => {
+                    expect_that!(&features, eq(Features::TEXTURE_COMPRESSION_BC))
+                }
+                _ => panic!("Unexpected result"),
+            }
+        }
+    }
+
+    #[test]
+    fn load_ktx2_partial_block_base_level() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let bytes = ktx2_bytes(145, 6, 4, &[vec![0; 32]]);
+        match Texture::from_ktx2(&instance, None, &bytes, TextureUsage::SAMPLED) {
+            Err(TextureLoadError::UnsupportedLayout(_)) => (),
+            _ => panic!("Unexpected result"),
+        }
+    }
+
+    #[test]
+    fn load_ktx2_unsupported_format() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let bytes = ktx2_bytes(23, 1, 1, &[vec![0; 3]]);
+        match Texture::from_ktx2(&instance, None, &bytes, TextureUsage::SAMPLED) {
+            Err(TextureLoadError::UnsupportedFormat(_)) => (),
+            _ => panic!("Unexpected result"),
+        }
+    }
+
+    #[test]
+    fn load_ktx2_truncated_data() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let bytes = ktx2_bytes(37, 2, 2, &[vec![0; 8]]);
+        match Texture::from_ktx2(&instance, None, &bytes, TextureUsage::SAMPLED) {
+            Err(TextureLoadError::DataSizeMismatch) => (),
+            _ => panic!("Unexpected result"),
+        }
+    }
+}
@@ -0,0 +1,934 @@
+use super::{
+    full_mip_level_count, Extent3d, FilterMode, Instance, Origin3d, Texture, TextureDataLayout,
+    TextureDescriptor, TextureDimension, TextureFormat, TextureFormatBlock, TextureUsage,
+};
+
+pub type Rgba32FImage = image::ImageBuffer<image::Rgba<f32>, Vec<f32>>;
+pub type Luma32FImage = image::ImageBuffer<image::Luma<f32>, Vec<f32>>;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy, serde::Serialize, serde::Deserialize)]
+pub enum ColorSpace {
+    Linear,
+    Srgb,
+}
+
+impl From<TextureFormat> for ColorSpace {
+    fn from(format: TextureFormat) -> Self {
+        match format {
+            TextureFormat::Rgba8UnormSrgb
+            | TextureFormat::Bgra8UnormSrgb
+            | TextureFormat::Bc1RgbaUnormSrgb
+            | TextureFormat::Bc2RgbaUnormSrgb
+            | TextureFormat::Bc3RgbaUnormSrgb
+            | TextureFormat::Bc7RgbaUnormSrgb => ColorSpace::Srgb,
+            _ => ColorSpace::Linear,
+        }
+    }
+}
+
+impl Texture {
+    pub fn from_image(
+        instance: &Instance,
+        label: Option<&str>,
+        img: &image::DynamicImage,
+        color_space: ColorSpace,
+        usage: TextureUsage,
+    ) -> Self {
+        let (format, bytes) = image_texel_bytes(img, color_space);
+        Self::from_texel_bytes(
+            instance,
+            label,
+            format,
+            image_size(img, 1),
+            1,
+            &bytes,
+            usage,
+        )
+    }
+
+    pub fn from_images(
+        instance: &Instance,
+        label: Option<&str>,
+        imgs: &[image::DynamicImage],
+        color_space: ColorSpace,
+        usage: TextureUsage,
+    ) -> Self {
+        assert!(
+            !imgs.is_empty(),
+            "A texture array requires at least one image"
+        );
+        let size = image_size(&imgs[0], imgs.len() as u32);
+        let mut array_format = None;
+        let mut array_bytes = Vec::new();
+        for img in imgs {
+            assert!(
+                image_size(img, size.depth) == size,
+                "All images of a texture array must have the same size"
+            );
+            let (format, bytes) = image_texel_bytes(img, color_space);
+            assert!(
+                *array_format.get_or_insert(format) == format,
+                "All images of a texture array must have the same texture format"
+            );
+            array_bytes.extend_from_slice(&bytes);
+        }
+        Self::from_texel_bytes(
+            instance,
+            label,
+            array_format.unwrap(),
+            size,
+            1,
+            &array_bytes,
+            usage,
+        )
+    }
+
+    // The faces are ordered as +X, -X, +Y, -Y, +Z, -Z.
+    pub fn from_cube_images(
+        instance: &Instance,
+        label: Option<&str>,
+        faces: &[image::DynamicImage; 6],
+        color_space: ColorSpace,
+        usage: TextureUsage,
+    ) -> Self {
+        let size = image_size(&faces[0], 6);
+        assert!(
+            size.width == size.height,
+            "The faces of a cube map must be square"
+        );
+        Self::from_images(instance, label, faces, color_space, usage)
+    }
+
+    pub fn from_image_with_mipmaps(
+        instance: &Instance,
+        label: Option<&str>,
+        img: &image::DynamicImage,
+        color_space: ColorSpace,
+        usage: TextureUsage,
+        filter: FilterMode,
+    ) -> Self {
+        let (format, bytes) = image_texel_bytes(img, color_space);
+        let size = image_size(img, 1);
+        let texture = Self::from_texel_bytes(
+            instance,
+            label,
+            format,
+            size,
+            full_mip_level_count(&size),
+            &bytes,
+            usage | TextureUsage::SAMPLED | TextureUsage::OUTPUT_ATTACHMENT,
+        );
+        texture.generate_mipmaps(instance, filter);
+        texture
+    }
+
+    pub fn from_rgba32f_image(
+        instance: &Instance,
+        label: Option<&str>,
+        img: &Rgba32FImage,
+        usage: TextureUsage,
+    ) -> Self {
+        let (width, height) = img.dimensions();
+        Self::from_texel_bytes(
+            instance,
+            label,
+            TextureFormat::Rgba32Float,
+            Extent3d {
+                width,
+                height,
+                depth: 1,
+            },
+            1,
+            bytemuck::cast_slice(img.as_raw()),
+            usage,
+        )
+    }
+
+    fn from_texel_bytes(
+        instance: &Instance,
+        label: Option<&str>,
+        format: TextureFormat,
+        size: Extent3d,
+        mip_level_count: u32,
+        bytes: &[u8],
+        usage: TextureUsage,
+    ) -> Self {
+        let texture = Self::new(
+            instance,
+            &TextureDescriptor {
+                label,
+                size,
+                mip_level_count,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format,
+                usage: usage | TextureUsage::COPY_DST,
+            },
+        );
+        texture.write(
+            instance,
+            0,
+            Origin3d::ZERO,
+            bytes,
+            TextureDataLayout {
+                offset: 0,
+                bytes_per_row: TextureFormatBlock::from(format).byte_count * size.width,
+                rows_per_image: size.height,
+            },
+            size,
+        );
+        texture
+    }
+
+    pub fn to_image(&self, instance: &Instance) -> image::RgbaImage {
+        self.to_dynamic_image(instance, None).to_rgba8()
+    }
+
+    pub fn to_image_region(
+        &self,
+        instance: &Instance,
+        mip_level: u32,
+        origin: Origin3d,
+        extent: Extent3d,
+    ) -> image::RgbaImage {
+        self.to_dynamic_image_region(instance, mip_level, origin, extent, None)
+            .to_rgba8()
+    }
+
+    // Float formats are clamped to [0, 1] and stored with 16 bits per
+    // channel, use to_rgba32f_image for lossless output.
+    pub fn to_dynamic_image(
+        &self,
+        instance: &Instance,
+        color_space: Option<ColorSpace>,
+    ) -> image::DynamicImage {
+        self.to_dynamic_image_region(
+            instance,
+            0,
+            Origin3d::ZERO,
+            self.mip_level_size(0),
+            color_space,
+        )
+    }
+
+    // The image crate has no floating point image type, float formats are
+    // therefore clamped to [0, 1] and stored with 16 bits per channel. Use
+    // to_rgba32f_image to retrieve the unclamped values.
+    pub fn to_dynamic_image_region(
+        &self,
+        instance: &Instance,
+        mip_level: u32,
+        origin: Origin3d,
+        extent: Extent3d,
+        color_space: Option<ColorSpace>,
+    ) -> image::DynamicImage {
+        assert!(
+            extent.depth == 1,
+            "Only a single texture layer can be converted to an image"
+        );
+        let bytes = self.read_bytes(instance, mip_level, origin, extent);
+        texel_bytes_to_dynamic_image(self.format(), extent, &bytes, color_space)
+    }
+
+    pub fn to_rgba32f_image(
+        &self,
+        instance: &Instance,
+        color_space: Option<ColorSpace>,
+    ) -> Rgba32FImage {
+        self.to_rgba32f_image_region(
+            instance,
+            0,
+            Origin3d::ZERO,
+            self.mip_level_size(0),
+            color_space,
+        )
+    }
+
+    pub fn to_rgba32f_image_region(
+        &self,
+        instance: &Instance,
+        mip_level: u32,
+        origin: Origin3d,
+        extent: Extent3d,
+        color_space: Option<ColorSpace>,
+    ) -> Rgba32FImage {
+        let samples = self.read_samples(instance, mip_level, origin, extent, color_space);
+        let values = if samples.channel_count == 1 {
+            samples
+                .values
+                .iter()
+                .flat_map(|v| vec![*v, *v, *v, 1.])
+                .collect()
+        } else {
+            samples.values
+        };
+        Rgba32FImage::from_raw(extent.width, extent.height, values).unwrap()
+    }
+
+    pub fn to_depth_image(&self, instance: &Instance) -> Luma32FImage {
+        self.to_depth_image_region(instance, 0, Origin3d::ZERO, self.mip_level_size(0))
+    }
+
+    // Depth24Plus formats have an implementation defined layout and can't be
+    // copied, only Depth32Float textures can be read back.
+    pub fn to_depth_image_region(
+        &self,
+        instance: &Instance,
+        mip_level: u32,
+        origin: Origin3d,
+        extent: Extent3d,
+    ) -> Luma32FImage {
+        assert!(
+            extent.depth == 1,
+            "Only a single texture layer can be converted to an image"
+        );
+        assert!(
+            self.format() == TextureFormat::Depth32Float,
+            "Only Depth32Float textures can be converted to a depth image"
+        );
+        let values = self
+            .read_bytes(instance, mip_level, origin, extent)
+            .chunks(4)
+            .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+            .collect();
+        Luma32FImage::from_raw(extent.width, extent.height, values).unwrap()
+    }
+
+    fn read_samples(
+        &self,
+        instance: &Instance,
+        mip_level: u32,
+        origin: Origin3d,
+        extent: Extent3d,
+        color_space: Option<ColorSpace>,
+    ) -> Samples {
+        assert!(
+            extent.depth == 1,
+            "Only a single texture layer can be converted to an image"
+        );
+        let bytes = self.read_bytes(instance, mip_level, origin, extent);
+        decode_samples(self.format(), &bytes, color_space)
+    }
+}
+
+pub(crate) fn texel_bytes_to_dynamic_image(
+    format: TextureFormat,
+    extent: Extent3d,
+    bytes: &[u8],
+    color_space: Option<ColorSpace>,
+) -> image::DynamicImage {
+    let samples = decode_samples(format, bytes, color_space);
+    match (samples.channel_count, samples.precision) {
+        (1, SamplePrecision::Low) => image::DynamicImage::ImageLuma8(
+            image::GrayImage::from_raw(
+                extent.width,
+                extent.height,
+                samples.values.iter().map(|v| to_u8(*v)).collect(),
+            )
+            .unwrap(),
+        ),
+        (1, SamplePrecision::High) => image::DynamicImage::ImageLuma16(
+            image::ImageBuffer::from_raw(
+                extent.width,
+                extent.height,
+                samples.values.iter().map(|v| to_u16(*v)).collect(),
+            )
+            .unwrap(),
+        ),
+        (_, SamplePrecision::Low) => image::DynamicImage::ImageRgba8(
+            image::RgbaImage::from_raw(
+                extent.width,
+                extent.height,
+                samples.values.iter().map(|v| to_u8(*v)).collect(),
+            )
+            .unwrap(),
+        ),
+        (_, SamplePrecision::High) => image::DynamicImage::ImageRgba16(
+            image::ImageBuffer::from_raw(
+                extent.width,
+                extent.height,
+                samples.values.iter().map(|v| to_u16(*v)).collect(),
+            )
+            .unwrap(),
+        ),
+    }
+}
+
+fn decode_samples(format: TextureFormat, bytes: &[u8], color_space: Option<ColorSpace>) -> Samples {
+    let mut samples = Samples::decode(format, bytes);
+    let source_color_space = ColorSpace::from(format);
+    match color_space {
+        Some(ColorSpace::Linear) if source_color_space == ColorSpace::Srgb => {
+            samples.convert_color(srgb_to_linear)
+        }
+        Some(ColorSpace::Srgb) if source_color_space == ColorSpace::Linear => {
+            samples.convert_color(linear_to_srgb)
+        }
+        _ => (),
+    }
+    samples
+}
+
+fn image_size(img: &image::DynamicImage, layer_count: u32) -> Extent3d {
+    let (width, height) = image::GenericImageView::dimensions(img);
+    Extent3d {
+        width,
+        height,
+        depth: layer_count,
+    }
+}
+
+// Images without a matching texture format are converted: 8 bit images
+// with more than one channel are expanded to RGBA, 16 bit images are
+// stored as half floats, in linear space if they were sRGB encoded.
+fn image_texel_bytes(
+    img: &image::DynamicImage,
+    color_space: ColorSpace,
+) -> (TextureFormat, Vec<u8>) {
+    match img {
+        image::DynamicImage::ImageLuma8(img) if color_space == ColorSpace::Linear => {
+            (TextureFormat::R8Unorm, img.as_raw().clone())
+        }
+        image::DynamicImage::ImageBgra8(img) => (
+            match color_space {
+                ColorSpace::Linear => TextureFormat::Bgra8Unorm,
+                ColorSpace::Srgb => TextureFormat::Bgra8UnormSrgb,
+            },
+            img.as_raw().clone(),
+        ),
+        image::DynamicImage::ImageLuma16(_)
+        | image::DynamicImage::ImageLumaA16(_)
+        | image::DynamicImage::ImageRgb16(_)
+        | image::DynamicImage::ImageRgba16(_) => {
+            let img = img.to_rgba16();
+            let bytes = img
+                .as_raw()
+                .chunks(4)
+                .flat_map(|c| {
+                    let mut texel = [0.; 4];
+                    for (i, v) in c.iter().enumerate() {
+                        texel[i] = *v as f32 / 65535.;
+                        if color_space == ColorSpace::Srgb && i != 3 {
+                            texel[i] = srgb_to_linear(texel[i]);
+                        }
+                    }
+                    texel.to_vec()
+                })
+                .flat_map(|v| f32_to_f16(v).to_le_bytes().to_vec())
+                .collect();
+            (TextureFormat::Rgba16Float, bytes)
+        }
+        _ => (
+            match color_space {
+                ColorSpace::Linear => TextureFormat::Rgba8Unorm,
+                ColorSpace::Srgb => TextureFormat::Rgba8UnormSrgb,
+            },
+            img.to_rgba8().into_raw(),
+        ),
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum SamplePrecision {
+    Low,
+    High,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+struct Samples {
+    values: Vec<f32>,
+    channel_count: usize,
+    precision: SamplePrecision,
+}
+
+impl Samples {
+    fn decode(format: TextureFormat, bytes: &[u8]) -> Self {
+        let (values, channel_count, precision) = match format {
+            TextureFormat::R8Unorm => (
+                bytes.iter().map(|v| *v as f32 / 255.).collect(),
+                1,
+                SamplePrecision::Low,
+            ),
+            TextureFormat::Rgba8Unorm | TextureFormat::Rgba8UnormSrgb => (
+                bytes.iter().map(|v| *v as f32 / 255.).collect(),
+                4,
+                SamplePrecision::Low,
+            ),
+            TextureFormat::Bgra8Unorm | TextureFormat::Bgra8UnormSrgb => (
+                bytes
+                    .chunks(4)
+                    .flat_map(|c| vec![c[2], c[1], c[0], c[3]])
+                    .map(|v| v as f32 / 255.)
+                    .collect(),
+                4,
+                SamplePrecision::Low,
+            ),
+            TextureFormat::Rgb10a2Unorm => (
+                bytes
+                    .chunks(4)
+                    .flat_map(|c| {
+                        let v = u32::from_le_bytes([c[0], c[1], c[2], c[3]]);
+                        vec![
+                            (v & 0x3ff) as f32 / 1023.,
+                            ((v >> 10) & 0x3ff) as f32 / 1023.,
+                            ((v >> 20) & 0x3ff) as f32 / 1023.,
+                            (v >> 30) as f32 / 3.,
+                        ]
+                    })
+                    .collect(),
+                4,
+                SamplePrecision::High,
+            ),
+            TextureFormat::R16Float => (
+                bytes
+                    .chunks(2)
+                    .map(|c| f16_to_f32(u16::from_le_bytes([c[0], c[1]])))
+                    .collect(),
+                1,
+                SamplePrecision::High,
+            ),
+            TextureFormat::Rgba16Float => (
+                bytes
+                    .chunks(2)
+                    .map(|c| f16_to_f32(u16::from_le_bytes([c[0], c[1]])))
+                    .collect(),
+                4,
+                SamplePrecision::High,
+            ),
+            TextureFormat::R32Float => (
+                bytes
+                    .chunks(4)
+                    .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+                    .collect(),
+                1,
+                SamplePrecision::High,
+            ),
+            TextureFormat::Rgba32Float => (
+                bytes
+                    .chunks(4)
+                    .map(|c| f32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+                    .collect(),
+                4,
+                SamplePrecision::High,
+            ),
+            _ => panic!("The texture format can't be converted to an image"),
+        };
+        Self {
+            values,
+            channel_count,
+            precision,
+        }
+    }
+
+    fn convert_color(&mut self, f: fn(f32) -> f32) {
+        let channel_count = self.channel_count;
+        for (i, v) in self.values.iter_mut().enumerate() {
+            // The alpha channel is always linear.
+            if channel_count == 1 || i % channel_count != 3 {
+                *v = f(*v);
+            }
+        }
+    }
+}
+
+fn to_u8(v: f32) -> u8 {
+    (v.max(0.).min(1.) * 255.).round() as u8
+}
+
+fn to_u16(v: f32) -> u16 {
+    (v.max(0.).min(1.) * 65535.).round() as u16
+}
+
+fn srgb_to_linear(v: f32) -> f32 {
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(v: f32) -> f32 {
+    if v <= 0.003_130_8 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1. / 2.4) - 0.055
+    }
+}
+
+fn f16_to_f32(bits: u16) -> f32 {
+    let sign = ((bits >> 15) as u32) << 31;
+    let exponent = ((bits >> 10) & 0x1f) as u32;
+    let mantissa = (bits & 0x3ff) as u32;
+    match exponent {
+        0 => {
+            let magnitude = mantissa as f32 / (1 << 24) as f32;
+            if sign == 0 {
+                magnitude
+            } else {
+                -magnitude
+            }
+        }
+        0x1f => f32::from_bits(sign | 0x7f80_0000 | (mantissa << 13)),
+        _ => f32::from_bits(sign | ((exponent + 112) << 23) | (mantissa << 13)),
+    }
+}
+
+fn f32_to_f16(v: f32) -> u16 {
+    let bits = v.to_bits();
+    let sign = ((bits >> 16) & 0x8000) as u16;
+    let exponent = ((bits >> 23) & 0xff) as i32;
+    let mantissa = bits & 0x7f_ffff;
+    if exponent == 0xff {
+        let nan_bit = if mantissa != 0 { 0x200 } else { 0 };
+        return sign | 0x7c00 | nan_bit;
+    }
+    let half_exponent = exponent - 127 + 15;
+    if half_exponent >= 0x1f {
+        return sign | 0x7c00;
+    }
+    if half_exponent <= 0 {
+        if half_exponent < -10 {
+            return sign;
+        }
+        let mantissa = mantissa | 0x80_0000;
+        let shift = (14 - half_exponent) as u32;
+        let round = (mantissa >> (shift - 1)) & 1;
+        return sign | ((mantissa >> shift) + round) as u16;
+    }
+    // A carry from the rounding correctly propagates into the exponent.
+    let round = (mantissa >> 12) & 1;
+    sign | ((((half_exponent as u32) << 10) | (mantissa >> 13)) + round) as u16
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use galvanic_assert::{matchers::*, *};
+
+    use crate::core::{
+        InstanceDescriptor, TextureDataLayout, TextureDescriptor, TextureDimension, TextureUsage,
+    };
+
+    fn create_texture(instance: &Instance, format: TextureFormat, data: &[u8]) -> Texture {
+        let block_size = crate::core::TextureFormatBlock::from(format).byte_count;
+        let width = data.len() as u32 / block_size;
+        let size = Extent3d {
+            width,
+            height: 1,
+            depth: 1,
+        };
+        let texture = Texture::new(
+            instance,
+            &TextureDescriptor {
+                label: None,
+                size,
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format,
+                usage: TextureUsage::COPY_SRC | TextureUsage::COPY_DST,
+            },
+        );
+        texture.write(
+            instance,
+            0,
+            Origin3d::ZERO,
+            data,
+            TextureDataLayout {
+                offset: 0,
+                bytes_per_row: data.len() as u32,
+                rows_per_image: 1,
+            },
+            size,
+        );
+        texture
+    }
+
+    #[test]
+    fn color_space_from_format() {
+        expect_that!(
+            &ColorSpace::from(TextureFormat::Bgra8UnormSrgb),
+            eq(ColorSpace::Srgb)
+        );
+        expect_that!(
+            &ColorSpace::from(TextureFormat::Rgba16Float),
+            eq(ColorSpace::Linear)
+        );
+    }
+
+    #[test]
+    fn f16_conversion() {
+        expect_that!(&f16_to_f32(0x0000), eq(0.));
+        expect_that!(&f16_to_f32(0x3c00), eq(1.));
+        expect_that!(&f16_to_f32(0xc000), eq(-2.));
+        expect_that!(&f16_to_f32(0x3800), eq(0.5));
+        expect_that!(&f16_to_f32(0x0001), eq(1. / (1 << 24) as f32));
+        expect_that!(&f16_to_f32(0x7c00), eq(std::f32::INFINITY));
+    }
+
+    #[test]
+    fn f16_round_trip() {
+        for v in &[0., 1., -2., 0.5, 0.1, 65504., 1e-4, 1. / (1 << 24) as f32] {
+            let half = f32_to_f16(*v);
+            expect_that!(
+                &f16_to_f32(half),
+                close_to(*v, v.abs() * 1e-3 + std::f32::EPSILON)
+            );
+        }
+        expect_that!(&f32_to_f16(1e6), eq(0x7c00));
+        expect_that!(&f32_to_f16(1e-10), eq(0));
+    }
+
+    #[test]
+    fn srgb_conversion() {
+        expect_that!(&srgb_to_linear(0.), close_to(0., 1e-6));
+        expect_that!(&srgb_to_linear(1.), close_to(1., 1e-6));
+        expect_that!(&srgb_to_linear(0.5), close_to(0.214_041, 1e-6));
+        expect_that!(&linear_to_srgb(0.214_041), close_to(0.5, 1e-6));
+    }
+
+    #[test]
+    fn bgra_to_image() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let texture = create_texture(
+            &instance,
+            TextureFormat::Bgra8UnormSrgb,
+            &[1, 2, 3, 4, 5, 6, 7, 8],
+        );
+        let image = texture.to_image(&instance);
+        expect_that!(&image.get_pixel(0, 0).0, eq([3, 2, 1, 4]));
+        expect_that!(&image.get_pixel(1, 0).0, eq([7, 6, 5, 8]));
+    }
+
+    #[test]
+    fn srgb_to_linear_image() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let texture = create_texture(
+            &instance,
+            TextureFormat::Rgba8UnormSrgb,
+            &[0, 128, 255, 128],
+        );
+        let image = texture
+            .to_dynamic_image(&instance, Some(ColorSpace::Linear))
+            .to_rgba8();
+        expect_that!(&image.get_pixel(0, 0).0, eq([0, 55, 255, 128]));
+
+        let image = texture
+            .to_dynamic_image(&instance, Some(ColorSpace::Srgb))
+            .to_rgba8();
+        expect_that!(&image.get_pixel(0, 0).0, eq([0, 128, 255, 128]));
+    }
+
+    #[test]
+    fn float_to_image() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let data = [0.5f32, 2., -1., 1.];
+        let texture = create_texture(
+            &instance,
+            TextureFormat::Rgba32Float,
+            bytemuck::cast_slice(&data),
+        );
+
+        let image = texture.to_rgba32f_image(&instance, None);
+        expect_that!(&image.get_pixel(0, 0).0, eq(data));
+
+        match texture.to_dynamic_image(&instance, None) {
+            image::DynamicImage::ImageRgba16(image) => {
+                expect_that!(&image.get_pixel(0, 0).0, eq([32768, 65535, 0, 65535]))
+            }
+            _ => panic!("Unexpected image type"),
+        }
+    }
+
+    #[test]
+    fn from_rgba_image() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let img = image::RgbaImage::from_fn(3, 2, |x, y| image::Rgba([x as u8, y as u8, 0, 255]));
+        let texture = Texture::from_image(
+            &instance,
+            None,
+            &image::DynamicImage::ImageRgba8(img.clone()),
+            ColorSpace::Srgb,
+            TextureUsage::COPY_SRC,
+        );
+        expect_that!(&texture.format(), eq(TextureFormat::Rgba8UnormSrgb));
+        expect_that!(
+            texture.size(),
+            eq(Extent3d {
+                width: 3,
+                height: 2,
+                depth: 1
+            })
+        );
+        expect_that!(&texture.to_image(&instance), eq(img));
+    }
+
+    #[test]
+    fn from_gray_image() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let img = image::GrayImage::from_fn(2, 5, |x, y| image::Luma([(x + 2 * y) as u8]));
+
+        let texture = Texture::from_image(
+            &instance,
+            None,
+            &image::DynamicImage::ImageLuma8(img.clone()),
+            ColorSpace::Linear,
+            TextureUsage::COPY_SRC,
+        );
+        expect_that!(&texture.format(), eq(TextureFormat::R8Unorm));
+        expect_that!(
+            &texture.to_dynamic_image(&instance, None).to_luma8(),
+            eq(img.clone())
+        );
+
+        let texture = Texture::from_image(
+            &instance,
+            None,
+            &image::DynamicImage::ImageLuma8(img.clone()),
+            ColorSpace::Srgb,
+            TextureUsage::COPY_SRC,
+        );
+        expect_that!(&texture.format(), eq(TextureFormat::Rgba8UnormSrgb));
+        expect_that!(
+            &texture.to_dynamic_image(&instance, None).to_luma8(),
+            eq(img)
+        );
+    }
+
+    #[test]
+    fn from_rgb_image() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let img = image::RgbImage::from_fn(5, 3, |x, y| image::Rgb([x as u8, y as u8, 7]));
+        let texture = Texture::from_image(
+            &instance,
+            None,
+            &image::DynamicImage::ImageRgb8(img),
+            ColorSpace::Linear,
+            TextureUsage::COPY_SRC,
+        );
+        expect_that!(&texture.format(), eq(TextureFormat::Rgba8Unorm));
+        let img = texture.to_image(&instance);
+        expect_that!(&img.dimensions(), eq((5, 3)));
+        expect_that!(&img.get_pixel(4, 1).0, eq([4, 1, 7, 255]));
+    }
+
+    #[test]
+    fn from_16_bit_image() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let img = image::ImageBuffer::<image::Rgba<u16>, Vec<u16>>::from_fn(1, 3, |_, y| {
+            image::Rgba([0, 65535, (16384 * y) as u16, 65535])
+        });
+        let texture = Texture::from_image(
+            &instance,
+            None,
+            &image::DynamicImage::ImageRgba16(img),
+            ColorSpace::Linear,
+            TextureUsage::COPY_SRC,
+        );
+        expect_that!(&texture.format(), eq(TextureFormat::Rgba16Float));
+        let img = texture.to_rgba32f_image(&instance, None);
+        expect_that!(&img.dimensions(), eq((1, 3)));
+        expect_that!(&img.get_pixel(0, 2).0[0], eq(0.));
+        expect_that!(&img.get_pixel(0, 2).0[1], eq(1.));
+        expect_that!(&img.get_pixel(0, 2).0[2], close_to(0.5, 1e-3));
+    }
+
+    #[test]
+    fn from_rgba32f_image() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let img = Rgba32FImage::from_fn(4, 1, |x, _| image::Rgba([x as f32, -1., 0.25, 1.]));
+        let texture = Texture::from_rgba32f_image(&instance, None, &img, TextureUsage::COPY_SRC);
+        expect_that!(&texture.format(), eq(TextureFormat::Rgba32Float));
+        expect_that!(&texture.to_rgba32f_image(&instance, None), eq(img));
+    }
+
+    #[test]
+    fn from_images() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let imgs: Vec<image::DynamicImage> = (0..3)
+            .map(|i| {
+                image::DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(
+                    3,
+                    2,
+                    image::Rgba([i, 0, 0, 255]),
+                ))
+            })
+            .collect();
+        let texture = Texture::from_images(
+            &instance,
+            None,
+            &imgs,
+            ColorSpace::Linear,
+            TextureUsage::COPY_SRC,
+        );
+        expect_that!(&texture.layer_count(), eq(3));
+        let img = texture.to_image_region(
+            &instance,
+            0,
+            Origin3d { x: 0, y: 0, z: 2 },
+            Extent3d {
+                width: 3,
+                height: 2,
+                depth: 1,
+            },
+        );
+        expect_that!(&img.get_pixel(2, 1).0, eq([2, 0, 0, 255]));
+    }
+
+    #[test]
+    #[should_panic(expected = "All images of a texture array must have the same size")]
+    fn from_images_size_mismatch() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let imgs = vec![
+            image::DynamicImage::ImageRgba8(image::RgbaImage::new(3, 2)),
+            image::DynamicImage::ImageRgba8(image::RgbaImage::new(2, 3)),
+        ];
+        Texture::from_images(
+            &instance,
+            None,
+            &imgs,
+            ColorSpace::Linear,
+            TextureUsage::SAMPLED,
+        );
+    }
+
+    #[test]
+    fn from_cube_images() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let faces = [
+            image::DynamicImage::ImageRgba8(image::RgbaImage::new(4, 4)),
+            image::DynamicImage::ImageRgba8(image::RgbaImage::new(4, 4)),
+            image::DynamicImage::ImageRgba8(image::RgbaImage::new(4, 4)),
+            image::DynamicImage::ImageRgba8(image::RgbaImage::new(4, 4)),
+            image::DynamicImage::ImageRgba8(image::RgbaImage::new(4, 4)),
+            image::DynamicImage::ImageRgba8(image::RgbaImage::new(4, 4)),
+        ];
+        let texture = Texture::from_cube_images(
+            &instance,
+            None,
+            &faces,
+            ColorSpace::Srgb,
+            TextureUsage::SAMPLED,
+        );
+        expect_that!(&texture.layer_count(), eq(6));
+        let _view = texture.create_cube_view();
+        let _view = texture.create_layer_view(5);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only Depth32Float textures can be converted to a depth image")]
+    fn depth_image_unsupported_format() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let texture = create_texture(&instance, TextureFormat::R32Float, &[0, 0, 0, 0]);
+        texture.to_depth_image(&instance);
+    }
+
+    #[test]
+    #[should_panic(expected = "The texture format can't be converted to an image")]
+    fn unsupported_format() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let texture = create_texture(&instance, TextureFormat::Rg8Unorm, &[1, 2]);
+        texture.to_image(&instance);
+    }
+}
@@ -0,0 +1,270 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use futures::FutureExt;
+
+use super::{
+    Buffer, BufferAddress, BufferAsyncError, BufferDescriptor, BufferUsage, Instance, MapMode,
+    TextureBufferSize,
+};
+
+#[derive(Debug)]
+pub(crate) struct StagingBuffer {
+    buffer: Buffer,
+    size: BufferAddress,
+}
+
+impl StagingBuffer {
+    pub fn buffer(&self) -> &Buffer {
+        &self.buffer
+    }
+}
+
+// Staging buffers are handed back to the pool once their content has been
+// received, so that continuous readbacks don't allocate a new buffer for
+// every frame.
+#[derive(Debug, Clone, Default)]
+pub struct StagingBufferPool {
+    free_buffers: Arc<Mutex<Vec<StagingBuffer>>>,
+}
+
+impl StagingBufferPool {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn free_buffer_count(&self) -> usize {
+        self.free_buffers.lock().unwrap().len()
+    }
+
+    pub fn clear(&self) {
+        self.free_buffers.lock().unwrap().clear();
+    }
+
+    pub(crate) fn acquire(&self, instance: &Instance, size: BufferAddress) -> StagingBuffer {
+        let mut free_buffers = self.free_buffers.lock().unwrap();
+        let best_fit = free_buffers
+            .iter()
+            .enumerate()
+            .filter(|(_, b)| b.size >= size)
+            .min_by_key(|(_, b)| b.size)
+            .map(|(i, _)| i);
+        match best_fit {
+            Some(i) => free_buffers.swap_remove(i),
+            None => StagingBuffer {
+                buffer: Buffer::new(
+                    instance,
+                    &BufferDescriptor {
+                        label: None,
+                        size,
+                        usage: BufferUsage::MAP_READ | BufferUsage::COPY_DST,
+                        mapped_at_creation: false,
+                    },
+                ),
+                size,
+            },
+        }
+    }
+
+    fn release(&self, buffer: StagingBuffer) {
+        self.free_buffers.lock().unwrap().push(buffer);
+    }
+}
+
+type MapFuture = Pin<Box<dyn Future<Output = Result<(), BufferAsyncError>> + Send>>;
+
+// The mapping only makes progress when the device is polled, either through
+// Instance::poll or implicitly by a later submission.
+pub struct TextureReadback {
+    buffer: Option<StagingBuffer>,
+    buffer_size: TextureBufferSize,
+    map_future: MapFuture,
+    pool: StagingBufferPool,
+}
+
+impl TextureReadback {
+    pub(crate) fn new(
+        buffer: StagingBuffer,
+        buffer_size: TextureBufferSize,
+        pool: StagingBufferPool,
+    ) -> Self {
+        let map_future = Box::pin(
+            buffer
+                .buffer
+                .slice(..buffer_size.byte_count())
+                .map_async(MapMode::Read),
+        );
+        Self {
+            buffer: Some(buffer),
+            buffer_size,
+            map_future,
+            pool,
+        }
+    }
+
+    pub fn is_received(&self) -> bool {
+        self.buffer.is_none()
+    }
+
+    pub fn try_receive(&mut self) -> Option<Result<Vec<u8>, BufferAsyncError>> {
+        if self.is_received() {
+            return None;
+        }
+        match self.map_future.as_mut().now_or_never() {
+            Some(result) => Some(self.receive(result)),
+            None => None,
+        }
+    }
+
+    fn receive(
+        &mut self,
+        result: Result<(), BufferAsyncError>,
+    ) -> Result<Vec<u8>, BufferAsyncError> {
+        let staging_buffer = self
+            .buffer
+            .take()
+            .expect("The texture readback has already been received");
+        result?;
+        let buffer_slice = staging_buffer.buffer.slice(..self.buffer_size.byte_count());
+        let padded_bytes = buffer_slice.get_mapped_range();
+        let bytes = self.buffer_size.unpad(&padded_bytes);
+        drop(padded_bytes);
+        staging_buffer.buffer.unmap();
+        self.pool.release(staging_buffer);
+        Ok(bytes)
+    }
+}
+
+impl std::fmt::Debug for TextureReadback {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.debug_struct("TextureReadback")
+            .field("buffer", &self.buffer)
+            .field("buffer_size", &self.buffer_size)
+            .field("pool", &self.pool)
+            .finish()
+    }
+}
+
+impl Future for TextureReadback {
+    type Output = Result<Vec<u8>, BufferAsyncError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        match self.map_future.as_mut().poll(cx) {
+            Poll::Ready(result) => Poll::Ready(self.receive(result)),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use galvanic_assert::{matchers::*, *};
+
+    use crate::core::{
+        Extent3d, InstanceDescriptor, Maintain, Origin3d, Texture, TextureDataLayout,
+        TextureDescriptor, TextureDimension, TextureFormat, TextureUsage,
+    };
+
+    fn create_texture(instance: &Instance) -> Texture {
+        let texture = Texture::new(
+            instance,
+            &TextureDescriptor {
+                label: None,
+                size: Extent3d {
+                    width: 3,
+                    height: 2,
+                    depth: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: TextureDimension::D2,
+                format: TextureFormat::Rgba8Unorm,
+                usage: TextureUsage::COPY_SRC | TextureUsage::COPY_DST,
+            },
+        );
+        let data: Vec<u8> = (0..24).collect();
+        texture.write(
+            instance,
+            0,
+            Origin3d::ZERO,
+            &data,
+            TextureDataLayout {
+                offset: 0,
+                bytes_per_row: 12,
+                rows_per_image: 2,
+            },
+            *texture.size(),
+        );
+        texture
+    }
+
+    #[test]
+    fn read_async() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let texture = create_texture(&instance);
+        let pool = StagingBufferPool::new();
+
+        let mut readback = texture.read_async(&instance, &pool, 0, Origin3d::ZERO, *texture.size());
+        expect_that!(!readback.is_received());
+        instance.poll(Maintain::Wait);
+        let bytes = readback.try_receive().unwrap().unwrap();
+        expect_that!(readback.is_received());
+        expect_that!(&bytes, eq((0..24).collect::<Vec<u8>>()));
+        expect_that!(readback.try_receive().is_none());
+    }
+
+    #[test]
+    fn read_async_blocking() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let texture = create_texture(&instance);
+        let pool = StagingBufferPool::new();
+
+        let readback = texture.read_async(
+            &instance,
+            &pool,
+            0,
+            Origin3d { x: 1, y: 1, z: 0 },
+            Extent3d {
+                width: 2,
+                height: 1,
+                depth: 1,
+            },
+        );
+        instance.poll(Maintain::Wait);
+        let bytes = futures::executor::block_on(readback).unwrap();
+        expect_that!(&bytes, eq((16..24).collect::<Vec<u8>>()));
+    }
+
+    #[test]
+    fn staging_buffer_reuse() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let texture = create_texture(&instance);
+        let pool = StagingBufferPool::new();
+
+        let mut readbacks: Vec<TextureReadback> = (0..3)
+            .map(|_| texture.read_async(&instance, &pool, 0, Origin3d::ZERO, *texture.size()))
+            .collect();
+        expect_that!(&pool.free_buffer_count(), eq(0));
+
+        instance.poll(Maintain::Wait);
+        for readback in readbacks.iter_mut() {
+            expect_that!(readback.try_receive().unwrap().is_ok());
+        }
+        expect_that!(&pool.free_buffer_count(), eq(3));
+
+        let mut readback = texture.read_async(&instance, &pool, 0, Origin3d::ZERO, *texture.size());
+        expect_that!(&pool.free_buffer_count(), eq(2));
+        instance.poll(Maintain::Wait);
+        expect_that!(readback.try_receive().unwrap().is_ok());
+        expect_that!(&pool.free_buffer_count(), eq(3));
+
+        pool.clear();
+        expect_that!(&pool.free_buffer_count(), eq(0));
+    }
+}
@@ -0,0 +1,777 @@
+use std::{borrow::Cow, collections::HashMap, fmt::Debug, marker::PhantomData, path::Path};
+
+use wgc::{
+    command::{RenderBundleEncoder, TextureCopyView},
+    device::trace::{Action, Command, FILE_NAME},
+    hub::{
+        GfxBackend, Global, GlobalIdentityHandlerFactory, IdentityHandler, IdentityHandlerFactory,
+    },
+    id,
+    id::TypedId,
+    resource::TextureViewDescriptor,
+};
+
+use super::{
+    texel_bytes_to_dynamic_image, BufferUsage, Extent3d, Origin3d, TextureBufferSize,
+    TextureDataLayout, TextureDimension, TextureFormat, TextureFormatBlock, TextureUsage,
+};
+
+#[derive(Debug)]
+pub enum TraceReplayError {
+    ReadFailed(std::io::Error),
+    ParsingFailed(ron::Error),
+    MissingInitialization,
+    UnsupportedBackend(String),
+    AdapterRequestFailed(String),
+    DeviceRequestFailed(String),
+    ActionFailed(String),
+    NoCanvas,
+}
+
+impl std::fmt::Display for TraceReplayError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TraceReplayError::ReadFailed(e) => write!(f, "Failed to read the trace ({})", e),
+            TraceReplayError::ParsingFailed(e) => write!(f, "Failed to parse the trace ({})", e),
+            TraceReplayError::MissingInitialization => {
+                write!(f, "The trace doesn't start with the device initialization")
+            }
+            TraceReplayError::UnsupportedBackend(backend) => write!(
+                f,
+                "The trace backend is not available on this platform ({})",
+                backend
+            ),
+            TraceReplayError::AdapterRequestFailed(e) => {
+                write!(f, "Failed to request an adapter ({})", e)
+            }
+            TraceReplayError::DeviceRequestFailed(e) => {
+                write!(f, "Failed to request a device ({})", e)
+            }
+            TraceReplayError::ActionFailed(e) => {
+                write!(f, "Failed to replay a trace action ({})", e)
+            }
+            TraceReplayError::NoCanvas => {
+                write!(f, "The trace doesn't present or render to any canvas")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TraceReplayError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TraceReplayError::ReadFailed(e) => Some(e),
+            TraceReplayError::ParsingFailed(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+// Replays a trace recorded through InstanceDescriptor::trace_path without
+// opening any window, and returns the content of the final canvas: the last
+// presented swap chain frame or, if nothing was presented, the first color
+// attachment of the last render pass. The image is produced by the same
+// conversion as Texture::to_dynamic_image.
+pub fn replay_trace<P: AsRef<Path>>(path: P) -> Result<image::DynamicImage, TraceReplayError> {
+    let dir = path.as_ref();
+    let mut text =
+        std::fs::read_to_string(dir.join(FILE_NAME)).map_err(TraceReplayError::ReadFailed)?;
+    // The closing bracket is only written when the traced device is dropped.
+    if !text.trim_end().ends_with(']') {
+        text.push(']');
+    }
+    let mut actions: Vec<Action> =
+        ron::de::from_str(&text).map_err(TraceReplayError::ParsingFailed)?;
+    if actions.is_empty() {
+        return Err(TraceReplayError::MissingInitialization);
+    }
+    let (desc, backend) = match actions.remove(0) {
+        Action::Init { desc, backend } => (desc, backend),
+        _ => return Err(TraceReplayError::MissingInitialization),
+    };
+
+    let global = Global::new("rae_gfx_replay", IdentityPassThroughFactory, backend.into());
+    let adapter = global
+        .request_adapter(
+            &wgc::instance::RequestAdapterOptions {
+                power_preference: wgt::PowerPreference::Default,
+                compatible_surface: None,
+            },
+            wgc::instance::AdapterInputs::IdSet(&[id::AdapterId::zip(0, 0, backend)], |id| {
+                id.backend()
+            }),
+        )
+        .map_err(|e| TraceReplayError::AdapterRequestFailed(e.to_string()))?;
+
+    match backend {
+        #[cfg(not(any(target_os = "ios", target_os = "macos")))]
+        wgt::Backend::Vulkan => {
+            replay::<wgc::backend::Vulkan>(&global, adapter, &desc, dir, actions)
+        }
+        #[cfg(any(target_os = "ios", target_os = "macos"))]
+        wgt::Backend::Metal => replay::<wgc::backend::Metal>(&global, adapter, &desc, dir, actions),
+        #[cfg(windows)]
+        wgt::Backend::Dx12 => replay::<wgc::backend::Dx12>(&global, adapter, &desc, dir, actions),
+        #[cfg(windows)]
+        wgt::Backend::Dx11 => replay::<wgc::backend::Dx11>(&global, adapter, &desc, dir, actions),
+        _ => Err(TraceReplayError::UnsupportedBackend(format!(
+            "{:?}",
+            backend
+        ))),
+    }
+}
+
+fn replay<B: GfxBackend>(
+    global: &Global<IdentityPassThroughFactory>,
+    adapter: id::AdapterId,
+    desc: &wgt::DeviceDescriptor,
+    dir: &Path,
+    actions: Vec<Action>,
+) -> Result<image::DynamicImage, TraceReplayError> {
+    let device = global
+        .adapter_request_device::<B>(
+            adapter,
+            desc,
+            None,
+            id::DeviceId::zip(1, 0, adapter.backend()),
+        )
+        .map_err(|e| TraceReplayError::DeviceRequestFailed(e.to_string()))?;
+    let mut replayer = Replayer::<B>::new(global, device, dir, &actions);
+    let result = actions
+        .into_iter()
+        .try_for_each(|action| replayer.process(action))
+        .and_then(|_| replayer.final_canvas());
+    let _ = global.device_poll::<B>(device, true);
+    global.device_drop::<B>(device);
+    result
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct CanvasTarget {
+    texture: id::TextureId,
+    mip_level: u32,
+    array_layer: u32,
+}
+
+struct Replayer<'a, B: GfxBackend> {
+    global: &'a Global<IdentityPassThroughFactory>,
+    device: id::DeviceId,
+    dir: &'a Path,
+    textures: HashMap<id::TextureId, (TextureFormat, Extent3d)>,
+    views: HashMap<id::TextureViewId, CanvasTarget>,
+    swap_chains: HashMap<id::SwapChainId, wgt::SwapChainDescriptor>,
+    frames: HashMap<id::SwapChainId, (id::TextureId, id::TextureViewId)>,
+    presented_frame: Option<id::TextureId>,
+    rendered_target: Option<CanvasTarget>,
+    rendered_image: Option<image::DynamicImage>,
+    // Indices of the objects created during the replay that aren't part of
+    // the trace.
+    next_texture_index: u32,
+    next_buffer_index: u32,
+    next_command_buffer_index: u32,
+    _backend: PhantomData<B>,
+}
+
+impl<'a, B: GfxBackend> Replayer<'a, B> {
+    fn new(
+        global: &'a Global<IdentityPassThroughFactory>,
+        device: id::DeviceId,
+        dir: &'a Path,
+        actions: &[Action],
+    ) -> Self {
+        let mut next_texture_index = 0;
+        let mut next_buffer_index = 0;
+        for action in actions {
+            match action {
+                Action::CreateTexture(id, _) => {
+                    next_texture_index = std::cmp::max(next_texture_index, id.unzip().0 + 1)
+                }
+                Action::CreateBuffer(id, _) => {
+                    next_buffer_index = std::cmp::max(next_buffer_index, id.unzip().0 + 1)
+                }
+                _ => (),
+            }
+        }
+        Self {
+            global,
+            device,
+            dir,
+            textures: HashMap::new(),
+            views: HashMap::new(),
+            swap_chains: HashMap::new(),
+            frames: HashMap::new(),
+            presented_frame: None,
+            rendered_target: None,
+            rendered_image: None,
+            next_texture_index,
+            next_buffer_index,
+            next_command_buffer_index: 0,
+            _backend: PhantomData,
+        }
+    }
+
+    fn process(&mut self, action: Action) -> Result<(), TraceReplayError> {
+        let global = self.global;
+        let device = self.device;
+        global
+            .device_maintain_ids::<B>(device)
+            .map_err(action_failed)?;
+        match action {
+            Action::Init { .. } => {
+                return Err(action_failed("the device is initialized more than once"))
+            }
+            Action::CreateBuffer(id, mut desc) => {
+                // Mapped buffers are initialized by the WriteBuffer actions
+                // recorded when they are unmapped.
+                if desc.mapped_at_creation && !desc.usage.contains(BufferUsage::MAP_WRITE) {
+                    desc.usage |= BufferUsage::COPY_DST;
+                }
+                desc.mapped_at_creation = false;
+                global
+                    .device_create_buffer::<B>(device, &desc, id)
+                    .map_err(action_failed)?;
+            }
+            Action::DestroyBuffer(id) => global.buffer_drop::<B>(id, true),
+            Action::CreateTexture(id, mut desc) => {
+                // Allows reading the final canvas back.
+                if desc.usage.contains(TextureUsage::OUTPUT_ATTACHMENT) && !is_depth(desc.format) {
+                    desc.usage |= TextureUsage::COPY_SRC;
+                }
+                self.textures.insert(id, (desc.format, desc.size));
+                global
+                    .device_create_texture::<B>(device, &desc, id)
+                    .map_err(action_failed)?;
+            }
+            Action::DestroyTexture(id) => {
+                if self.rendered_target.map(|t| t.texture) == Some(id) {
+                    let target = self.rendered_target.take().unwrap();
+                    self.rendered_image = Some(self.read_target(target)?);
+                }
+                self.textures.remove(&id);
+                global.texture_drop::<B>(id);
+            }
+            Action::CreateTextureView {
+                id,
+                parent_id,
+                desc,
+            } => {
+                self.views.insert(
+                    id,
+                    CanvasTarget {
+                        texture: parent_id,
+                        mip_level: desc.base_mip_level,
+                        array_layer: desc.base_array_layer,
+                    },
+                );
+                global
+                    .texture_create_view::<B>(parent_id, &desc, id)
+                    .map_err(action_failed)?;
+            }
+            Action::DestroyTextureView(id) => {
+                self.views.remove(&id);
+                global.texture_view_drop::<B>(id).map_err(action_failed)?;
+            }
+            Action::CreateSampler(id, desc) => {
+                global
+                    .device_create_sampler::<B>(device, &desc, id)
+                    .map_err(action_failed)?;
+            }
+            Action::DestroySampler(id) => global.sampler_drop::<B>(id),
+            // Swap chains are emulated with textures, since the replay has no
+            // surface to present to.
+            Action::CreateSwapChain(id, desc) => {
+                self.drop_frame(id)?;
+                self.swap_chains.insert(id, desc);
+            }
+            Action::GetSwapChainTexture { id, parent_id } => {
+                self.drop_frame(parent_id)?;
+                if let Some(view_id) = id {
+                    self.acquire_frame(parent_id, view_id)?;
+                }
+            }
+            Action::PresentSwapChain(id) => {
+                if let Some((texture, view)) = self.frames.remove(&id) {
+                    self.views.remove(&view);
+                    global.texture_view_drop::<B>(view).map_err(action_failed)?;
+                    if let Some(previous_texture) = self.presented_frame.replace(texture) {
+                        self.textures.remove(&previous_texture);
+                        global.texture_drop::<B>(previous_texture);
+                    }
+                    // The frame view id can be reused as soon as the frame is
+                    // presented.
+                    global
+                        .device_poll::<B>(device, true)
+                        .map_err(action_failed)?;
+                }
+            }
+            Action::CreateBindGroupLayout(id, desc) => {
+                global
+                    .device_create_bind_group_layout::<B>(device, &desc, id)
+                    .map_err(action_failed)?;
+            }
+            Action::DestroyBindGroupLayout(id) => global.bind_group_layout_drop::<B>(id),
+            Action::CreatePipelineLayout(id, desc) => {
+                global
+                    .device_create_pipeline_layout::<B>(device, &desc, id)
+                    .map_err(action_failed)?;
+            }
+            Action::DestroyPipelineLayout(id) => global.pipeline_layout_drop::<B>(id),
+            Action::CreateBindGroup(id, desc) => {
+                global
+                    .device_create_bind_group::<B>(device, &desc, id)
+                    .map_err(action_failed)?;
+            }
+            Action::DestroyBindGroup(id) => global.bind_group_drop::<B>(id),
+            Action::CreateShaderModule { id, data } => {
+                let words = self
+                    .read_data(&data)?
+                    .chunks(4)
+                    .map(|c| u32::from_le_bytes([c[0], c[1], c[2], c[3]]))
+                    .collect::<Vec<_>>();
+                global
+                    .device_create_shader_module::<B>(
+                        device,
+                        wgc::pipeline::ShaderModuleSource::SpirV(Cow::Owned(words)),
+                        id,
+                    )
+                    .map_err(action_failed)?;
+            }
+            Action::DestroyShaderModule(id) => global.shader_module_drop::<B>(id),
+            Action::CreateComputePipeline(id, desc) => {
+                global
+                    .device_create_compute_pipeline::<B>(device, &desc, id, None)
+                    .map_err(action_failed)?;
+            }
+            Action::DestroyComputePipeline(id) => global.compute_pipeline_drop::<B>(id),
+            Action::CreateRenderPipeline(id, desc) => {
+                global
+                    .device_create_render_pipeline::<B>(device, &desc, id, None)
+                    .map_err(action_failed)?;
+            }
+            Action::DestroyRenderPipeline(id) => global.render_pipeline_drop::<B>(id),
+            Action::CreateRenderBundle { id, desc, base } => {
+                let encoder =
+                    RenderBundleEncoder::new(&desc, device, Some(base)).map_err(action_failed)?;
+                global
+                    .render_bundle_encoder_finish::<B>(
+                        encoder,
+                        &wgt::RenderBundleDescriptor { label: desc.label },
+                        id,
+                    )
+                    .map_err(action_failed)?;
+            }
+            Action::DestroyRenderBundle(id) => global.render_bundle_drop::<B>(id),
+            Action::WriteBuffer {
+                id,
+                data,
+                range,
+                queued,
+            } => {
+                let bytes = self.read_data(&data)?;
+                if queued {
+                    global
+                        .queue_write_buffer::<B>(device, id, range.start, &bytes)
+                        .map_err(action_failed)?;
+                } else {
+                    global
+                        .device_set_buffer_sub_data::<B>(device, id, range.start, &bytes)
+                        .map_err(action_failed)?;
+                }
+            }
+            Action::WriteTexture {
+                to,
+                data,
+                layout,
+                size,
+            } => {
+                let bytes = self.read_data(&data)?;
+                global
+                    .queue_write_texture::<B>(device, &to, &bytes, &layout, &size)
+                    .map_err(action_failed)?;
+            }
+            Action::Submit(_, commands) => {
+                let encoder = self.create_command_encoder()?;
+                for command in commands {
+                    self.encode(encoder, command)?;
+                }
+                self.submit(encoder)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn encode(
+        &mut self,
+        encoder: id::CommandEncoderId,
+        command: Command,
+    ) -> Result<(), TraceReplayError> {
+        let global = self.global;
+        match command {
+            Command::CopyBufferToBuffer {
+                src,
+                src_offset,
+                dst,
+                dst_offset,
+                size,
+            } => global
+                .command_encoder_copy_buffer_to_buffer::<B>(
+                    encoder, src, src_offset, dst, dst_offset, size,
+                )
+                .map_err(action_failed),
+            Command::CopyBufferToTexture { src, dst, size } => global
+                .command_encoder_copy_buffer_to_texture::<B>(encoder, &src, &dst, &size)
+                .map_err(action_failed),
+            Command::CopyTextureToBuffer { src, dst, size } => global
+                .command_encoder_copy_texture_to_buffer::<B>(encoder, &src, &dst, &size)
+                .map_err(action_failed),
+            Command::CopyTextureToTexture { src, dst, size } => global
+                .command_encoder_copy_texture_to_texture::<B>(encoder, &src, &dst, &size)
+                .map_err(action_failed),
+            Command::RunComputePass { base } => global
+                .command_encoder_run_compute_pass_impl::<B>(encoder, base.as_ref())
+                .map_err(action_failed),
+            Command::RunRenderPass {
+                base,
+                target_colors,
+                target_depth_stencil,
+            } => {
+                global
+                    .command_encoder_run_render_pass_impl::<B>(
+                        encoder,
+                        base.as_ref(),
+                        &target_colors,
+                        target_depth_stencil.as_ref(),
+                    )
+                    .map_err(action_failed)?;
+                // Multisampled color buffers are resolved into the canvas.
+                if let Some(attachment) = target_colors.first() {
+                    let view = attachment.resolve_target.unwrap_or(attachment.attachment);
+                    if let Some(target) = self.views.get(&view) {
+                        self.rendered_target = Some(*target);
+                        self.rendered_image = None;
+                    }
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn final_canvas(&mut self) -> Result<image::DynamicImage, TraceReplayError> {
+        if let Some(texture) = self.presented_frame {
+            return self.read_target(CanvasTarget {
+                texture,
+                mip_level: 0,
+                array_layer: 0,
+            });
+        }
+        if let Some(target) = self.rendered_target {
+            return self.read_target(target);
+        }
+        self.rendered_image.take().ok_or(TraceReplayError::NoCanvas)
+    }
+
+    fn acquire_frame(
+        &mut self,
+        swap_chain: id::SwapChainId,
+        view: id::TextureViewId,
+    ) -> Result<(), TraceReplayError> {
+        let desc = match self.swap_chains.get(&swap_chain) {
+            Some(desc) => desc,
+            None => return Err(action_failed("the swap chain doesn't exist")),
+        };
+        let size = Extent3d {
+            width: desc.width,
+            height: desc.height,
+            depth: 1,
+        };
+        let format = desc.format;
+        let texture = id::TextureId::zip(self.next_texture_index, 1, self.device.backend());
+        self.next_texture_index += 1;
+        self.global
+            .device_create_texture::<B>(
+                self.device,
+                &wgt::TextureDescriptor {
+                    label: None,
+                    size,
+                    mip_level_count: 1,
+                    sample_count: 1,
+                    dimension: TextureDimension::D2,
+                    format,
+                    usage: desc.usage | TextureUsage::COPY_SRC,
+                },
+                texture,
+            )
+            .map_err(action_failed)?;
+        self.textures.insert(texture, (format, size));
+        self.global
+            .texture_create_view::<B>(
+                texture,
+                &TextureViewDescriptor {
+                    label: None,
+                    format: None,
+                    dimension: None,
+                    aspect: wgt::TextureAspect::All,
+                    base_mip_level: 0,
+                    level_count: None,
+                    base_array_layer: 0,
+                    array_layer_count: None,
+                },
+                view,
+            )
+            .map_err(action_failed)?;
+        self.views.insert(
+            view,
+            CanvasTarget {
+                texture,
+                mip_level: 0,
+                array_layer: 0,
+            },
+        );
+        self.frames.insert(swap_chain, (texture, view));
+        Ok(())
+    }
+
+    fn drop_frame(&mut self, swap_chain: id::SwapChainId) -> Result<(), TraceReplayError> {
+        if let Some((texture, view)) = self.frames.remove(&swap_chain) {
+            self.views.remove(&view);
+            self.global
+                .texture_view_drop::<B>(view)
+                .map_err(action_failed)?;
+            self.textures.remove(&texture);
+            self.global.texture_drop::<B>(texture);
+        }
+        Ok(())
+    }
+
+    fn read_target(
+        &mut self,
+        target: CanvasTarget,
+    ) -> Result<image::DynamicImage, TraceReplayError> {
+        let (format, size) = match self.textures.get(&target.texture) {
+            Some(texture) => *texture,
+            None => return Err(TraceReplayError::NoCanvas),
+        };
+        let extent = Extent3d {
+            width: std::cmp::max(size.width >> target.mip_level, 1),
+            height: std::cmp::max(size.height >> target.mip_level, 1),
+            depth: 1,
+        };
+        let buffer_size = TextureBufferSize::new(&extent, &TextureFormatBlock::from(format));
+        let buffer = id::BufferId::zip(self.next_buffer_index, 1, self.device.backend());
+        self.next_buffer_index += 1;
+        self.global
+            .device_create_buffer::<B>(
+                self.device,
+                &wgt::BufferDescriptor {
+                    label: None,
+                    size: buffer_size.byte_count(),
+                    usage: BufferUsage::MAP_READ | BufferUsage::COPY_DST,
+                    mapped_at_creation: false,
+                },
+                buffer,
+            )
+            .map_err(action_failed)?;
+
+        let encoder = self.create_command_encoder()?;
+        self.global
+            .command_encoder_copy_texture_to_buffer::<B>(
+                encoder,
+                &TextureCopyView {
+                    texture: target.texture,
+                    mip_level: target.mip_level,
+                    origin: Origin3d {
+                        x: 0,
+                        y: 0,
+                        z: target.array_layer,
+                    },
+                },
+                &wgc::command::BufferCopyView {
+                    buffer,
+                    layout: TextureDataLayout {
+                        offset: 0,
+                        bytes_per_row: buffer_size.padded_bytes_per_row as u32,
+                        rows_per_image: extent.height,
+                    },
+                },
+                &extent,
+            )
+            .map_err(action_failed)?;
+        self.submit(encoder)?;
+
+        let mut padded_bytes = vec![0; buffer_size.byte_count() as usize];
+        self.global
+            .device_wait_for_buffer::<B>(self.device, buffer)
+            .map_err(action_failed)?;
+        self.global
+            .device_get_buffer_sub_data::<B>(self.device, buffer, 0, &mut padded_bytes)
+            .map_err(action_failed)?;
+        self.global.buffer_drop::<B>(buffer, true);
+        Ok(texel_bytes_to_dynamic_image(
+            format,
+            extent,
+            &buffer_size.unpad(&padded_bytes),
+            None,
+        ))
+    }
+
+    fn read_data(&self, name: &str) -> Result<Vec<u8>, TraceReplayError> {
+        std::fs::read(self.dir.join(name)).map_err(TraceReplayError::ReadFailed)
+    }
+
+    fn create_command_encoder(&mut self) -> Result<id::CommandEncoderId, TraceReplayError> {
+        let encoder =
+            id::CommandEncoderId::zip(self.next_command_buffer_index, 1, self.device.backend());
+        self.next_command_buffer_index += 1;
+        self.global
+            .device_create_command_encoder::<B>(
+                self.device,
+                &wgt::CommandEncoderDescriptor { label: None },
+                encoder,
+            )
+            .map_err(action_failed)
+    }
+
+    fn submit(&self, encoder: id::CommandEncoderId) -> Result<(), TraceReplayError> {
+        let command_buffer = self
+            .global
+            .command_encoder_finish::<B>(encoder, &wgt::CommandBufferDescriptor { label: None })
+            .map_err(action_failed)?;
+        self.global
+            .queue_submit::<B>(self.device, &[command_buffer])
+            .map_err(action_failed)
+    }
+}
+
+fn action_failed<E: std::fmt::Display>(e: E) -> TraceReplayError {
+    TraceReplayError::ActionFailed(e.to_string())
+}
+
+fn is_depth(format: TextureFormat) -> bool {
+    matches!(
+        format,
+        TextureFormat::Depth32Float
+            | TextureFormat::Depth24Plus
+            | TextureFormat::Depth24PlusStencil8
+    )
+}
+
+// The replayed objects keep the ids they had in the trace.
+#[derive(Debug)]
+struct IdentityPassThrough<I>(PhantomData<I>);
+
+impl<I: Clone + Debug + TypedId> IdentityHandler<I> for IdentityPassThrough<I> {
+    type Input = I;
+
+    fn process(&self, id: I, backend: wgt::Backend) -> I {
+        let (index, epoch, _) = id.unzip();
+        I::zip(index, epoch, backend)
+    }
+
+    fn free(&self, _id: I) {}
+}
+
+#[derive(Debug)]
+struct IdentityPassThroughFactory;
+
+impl<I: Clone + Debug + TypedId> IdentityHandlerFactory<I> for IdentityPassThroughFactory {
+    type Filter = IdentityPassThrough<I>;
+
+    fn spawn(&self, _min_index: u32) -> Self::Filter {
+        IdentityPassThrough(PhantomData)
+    }
+}
+
+impl GlobalIdentityHandlerFactory for IdentityPassThroughFactory {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use galvanic_assert::{matchers::*, *};
+
+    use crate::core::{
+        Canvas, CanvasColorBufferFormat, CanvasColorBufferUsage, CanvasSize, CanvasTexture,
+        CanvasTextureColorBufferDescriptor, CanvasTextureDescriptor, ColorF64, ColorOperations,
+        CommandSequence, Instance, InstanceDescriptor, LoadOp, RenderPassOperations, SampleCount,
+    };
+
+    fn record_cleared_canvas(trace_path: &Path, sample_count: SampleCount) {
+        let _ = std::fs::remove_dir_all(trace_path);
+        let instance = Instance::new(&InstanceDescriptor {
+            trace_path: Some(trace_path.to_path_buf()),
+            ..InstanceDescriptor::default()
+        })
+        .unwrap();
+        let mut texture = CanvasTexture::new(
+            &instance,
+            &CanvasTextureDescriptor {
+                size: CanvasSize::new(3, 2),
+                sample_count,
+                color_buffer_descriptors: vec![CanvasTextureColorBufferDescriptor {
+                    format: CanvasColorBufferFormat::Rgba8Unorm,
+                    usage: CanvasColorBufferUsage::empty(),
+                }],
+                ..CanvasTextureDescriptor::default()
+            },
+        )
+        .unwrap();
+        let requirements = texture.render_pass_requirements();
+        let mut cmd_seq = CommandSequence::new(&instance);
+        {
+            let frame = texture.current_frame(&instance).unwrap();
+            let _rpass = cmd_seq
+                .begin_render_pass(
+                    &frame,
+                    &requirements,
+                    &RenderPassOperations {
+                        color_operations: vec![ColorOperations {
+                            load: LoadOp::Clear(ColorF64 {
+                                r: 1.,
+                                g: 0.,
+                                b: 0.,
+                                a: 1.,
+                            }),
+                            store: true,
+                        }],
+                        ..RenderPassOperations::default()
+                    },
+                )
+                .unwrap();
+        }
+        cmd_seq.submit(&instance);
+    }
+
+    #[test]
+    fn replay_canvas_texture() {
+        let trace_path = std::env::temp_dir().join("rae_gfx_replay_canvas_texture");
+        record_cleared_canvas(&trace_path, 1);
+
+        let image = replay_trace(&trace_path).unwrap().to_rgba8();
+        expect_that!(&image.dimensions(), eq((3, 2)));
+        for pixel in image.pixels() {
+            expect_that!(&pixel.0, eq([255, 0, 0, 255]));
+        }
+    }
+
+    #[test]
+    fn replay_multisampled_canvas_texture() {
+        let trace_path = std::env::temp_dir().join("rae_gfx_replay_multisampled_canvas_texture");
+        record_cleared_canvas(&trace_path, 2);
+
+        let image = replay_trace(&trace_path).unwrap().to_rgba8();
+        expect_that!(&image.dimensions(), eq((3, 2)));
+        for pixel in image.pixels() {
+            expect_that!(&pixel.0, eq([255, 0, 0, 255]));
+        }
+    }
+
+    #[test]
+    fn replay_missing_trace() {
+        let trace_path = std::env::temp_dir().join("rae_gfx_replay_missing_trace");
+        let _ = std::fs::remove_dir_all(&trace_path);
+        match replay_trace(&trace_path) {
+            Err(TraceReplayError::ReadFailed(_)) => (),
+            _ => panic!("The replay of a missing trace should fail"),
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use std::{cell::Cell, marker::PhantomData, num::NonZeroU64};
+
+use elsa::FrozenVec;
+
+use super::{
+    BindGroup, BindGroupDescriptor, BindGroupEntry, BindGroupLayout, BindGroupLayoutDescriptor,
+    BindGroupLayoutEntry, BindingResource, BindingType, Buffer, BufferAddress, BufferDescriptor,
+    BufferUsage, DeviceHandle, DynamicOffset, Instance, ShaderStage, BIND_BUFFER_ALIGNMENT,
+};
+
+// Ring of uniform slots addressed through a dynamic offset. Pushed values
+// reach the buffer at the next submission, each of them gets its own slot so
+// that they don't overwrite each other. When all slots are waiting for a
+// submission, the ring grows by as many slots as the initial capacity.
+// Persistent values get slots outside of the ring, which are never reused.
+// The buffer must be recreated when the instance is recreated.
+pub struct DynamicUniformBuffer<T: bytemuck::Pod> {
+    device: DeviceHandle,
+    bind_group_layout: BindGroupLayout,
+    chunk_capacity: u32,
+    chunks: FrozenVec<Box<Chunk>>,
+    next_slot: Cell<u32>,
+    submission: Cell<u64>,
+    pending_count: Cell<u32>,
+    persistent_chunks: FrozenVec<Box<Chunk>>,
+    persistent_count: Cell<u32>,
+    _p: PhantomData<T>,
+}
+
+// Buffer and bind group holding a fixed number of slots.
+#[derive(Debug)]
+struct Chunk {
+    buffer: Buffer,
+    bind_group: BindGroup,
+}
+
+impl<T: bytemuck::Pod> DynamicUniformBuffer<T> {
+    pub fn new(instance: &Instance, visibility: ShaderStage, capacity: u32) -> Self {
+        assert!(
+            capacity > 0,
+            "A dynamic uniform buffer can't have zero capacity"
+        );
+        let bind_group_layout = BindGroupLayout::new(
+            instance,
+            &BindGroupLayoutDescriptor {
+                label: None,
+                entries: &[BindGroupLayoutEntry {
+                    binding: 0,
+                    visibility,
+                    ty: BindingType::UniformBuffer {
+                        dynamic: true,
+                        min_binding_size: NonZeroU64::new(Self::element_size()),
+                    },
+                    count: None,
+                }],
+            },
+        );
+        let device = instance.device_handle();
+        let buffer = Self {
+            submission: Cell::new(device.submission_count()),
+            device,
+            bind_group_layout,
+            chunk_capacity: capacity,
+            chunks: FrozenVec::new(),
+            next_slot: Cell::new(0),
+            pending_count: Cell::new(0),
+            persistent_chunks: FrozenVec::new(),
+            persistent_count: Cell::new(0),
+            _p: PhantomData,
+        };
+        buffer.chunks.push(Box::new(buffer.create_chunk()));
+        buffer
+    }
+
+    // Number of slots in the ring, including the ones added when growing.
+    pub fn capacity(&self) -> u32 {
+        self.chunks.len() as u32 * self.chunk_capacity
+    }
+
+    pub fn bind_group_layout(&self) -> &BindGroupLayout {
+        &self.bind_group_layout
+    }
+
+    pub fn push(&self, value: &T) -> (&BindGroup, DynamicOffset) {
+        let submission = self.device.submission_count();
+        if submission != self.submission.get() {
+            self.submission.set(submission);
+            self.pending_count.set(0);
+        }
+        let mut capacity = self.capacity();
+        if self.pending_count.get() == capacity {
+            self.chunks.push(Box::new(self.create_chunk()));
+            self.next_slot.set(capacity);
+            capacity += self.chunk_capacity;
+        }
+        self.pending_count.set(self.pending_count.get() + 1);
+
+        let slot = self.next_slot.get();
+        self.next_slot.set((slot + 1) % capacity);
+        self.write_slot(&self.chunks, slot, value)
+    }
+
+    // The value is never overwritten, e.g. for render bundles which can be
+    // executed any number of times after being recorded. The slots are only
+    // released when the buffer is dropped.
+    pub fn push_persistent(&self, value: &T) -> (&BindGroup, DynamicOffset) {
+        let slot = self.persistent_count.get();
+        self.persistent_count.set(slot + 1);
+        if slot / self.chunk_capacity == self.persistent_chunks.len() as u32 {
+            self.persistent_chunks.push(Box::new(self.create_chunk()));
+        }
+        self.write_slot(&self.persistent_chunks, slot, value)
+    }
+
+    fn write_slot<'a>(
+        &self,
+        chunks: &'a FrozenVec<Box<Chunk>>,
+        slot: u32,
+        value: &T,
+    ) -> (&'a BindGroup, DynamicOffset) {
+        let chunk = &chunks[(slot / self.chunk_capacity) as usize];
+        let offset = (slot % self.chunk_capacity) as BufferAddress * Self::slot_size();
+        self.device
+            .write_buffer(&chunk.buffer, offset, bytemuck::bytes_of(value));
+        (&chunk.bind_group, offset as DynamicOffset)
+    }
+
+    fn create_chunk(&self) -> Chunk {
+        let buffer = self.device.create_buffer(&BufferDescriptor {
+            label: None,
+            size: Self::slot_size() * self.chunk_capacity as BufferAddress,
+            usage: BufferUsage::UNIFORM | BufferUsage::COPY_DST,
+            mapped_at_creation: false,
+        });
+        let bind_group = self.device.create_bind_group(&BindGroupDescriptor {
+            label: None,
+            layout: &self.bind_group_layout,
+            entries: &[BindGroupEntry {
+                binding: 0,
+                resource: BindingResource::Buffer(buffer.slice(0..Self::element_size())),
+            }],
+        });
+        Chunk { buffer, bind_group }
+    }
+
+    fn element_size() -> BufferAddress {
+        std::mem::size_of::<T>() as BufferAddress
+    }
+
+    fn slot_size() -> BufferAddress {
+        let align = BIND_BUFFER_ALIGNMENT;
+        (Self::element_size() + align - 1) / align * align
+    }
+}
+
+impl<T: bytemuck::Pod> std::fmt::Debug for DynamicUniformBuffer<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DynamicUniformBuffer")
+            .field("bind_group_layout", &self.bind_group_layout)
+            .field("capacity", &self.capacity())
+            .field("persistent_count", &self.persistent_count)
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use galvanic_assert::{matchers::*, *};
+
+    use crate::core::InstanceDescriptor;
+
+    #[test]
+    fn push() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let buffer = DynamicUniformBuffer::<[f32; 4]>::new(&instance, ShaderStage::VERTEX, 3);
+        expect_that!(&buffer.capacity(), eq(3));
+        expect_that!(&buffer.push(&[1., 2., 3., 4.]).1, eq(0));
+        expect_that!(&buffer.push(&[1., 2., 3., 4.]).1, eq(256));
+        instance.submit(std::iter::empty());
+        expect_that!(&buffer.push(&[1., 2., 3., 4.]).1, eq(512));
+        expect_that!(&buffer.push(&[1., 2., 3., 4.]).1, eq(0));
+        expect_that!(&buffer.push(&[1., 2., 3., 4.]).1, eq(256));
+        expect_that!(&buffer.capacity(), eq(3));
+    }
+
+    #[test]
+    fn push_grow() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let buffer = DynamicUniformBuffer::<[f32; 4]>::new(&instance, ShaderStage::VERTEX, 2);
+        let (first_bind_group, _) = buffer.push(&[1., 2., 3., 4.]);
+        expect_that!(&buffer.push(&[1., 2., 3., 4.]).1, eq(256));
+
+        // The ring grows instead of overwriting a pending slot.
+        let (bind_group, offset) = buffer.push(&[1., 2., 3., 4.]);
+        expect_that!(&offset, eq(0));
+        expect_that!(!std::ptr::eq(bind_group, first_bind_group));
+        expect_that!(&buffer.capacity(), eq(4));
+        expect_that!(&buffer.push(&[1., 2., 3., 4.]).1, eq(256));
+
+        instance.submit(std::iter::empty());
+        let (bind_group, offset) = buffer.push(&[1., 2., 3., 4.]);
+        expect_that!(&offset, eq(0));
+        expect_that!(std::ptr::eq(bind_group, first_bind_group));
+        expect_that!(&buffer.capacity(), eq(4));
+    }
+
+    #[test]
+    fn push_persistent() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let buffer = DynamicUniformBuffer::<[f32; 4]>::new(&instance, ShaderStage::VERTEX, 2);
+        expect_that!(&buffer.push_persistent(&[1., 2., 3., 4.]).1, eq(0));
+        expect_that!(&buffer.push_persistent(&[1., 2., 3., 4.]).1, eq(256));
+        instance.submit(std::iter::empty());
+        expect_that!(&buffer.push_persistent(&[1., 2., 3., 4.]).1, eq(0));
+        expect_that!(&buffer.persistent_chunks.len(), eq(2));
+
+        // Persistent slots don't take space in the ring.
+        expect_that!(&buffer.push(&[1., 2., 3., 4.]).1, eq(0));
+        expect_that!(&buffer.push(&[1., 2., 3., 4.]).1, eq(256));
+        expect_that!(&buffer.capacity(), eq(2));
+    }
+
+    #[test]
+    #[should_panic(expected = "A dynamic uniform buffer can't have zero capacity")]
+    fn zero_capacity() {
+        let instance = Instance::new(&InstanceDescriptor::default()).unwrap();
+        let _buffer = DynamicUniformBuffer::<[f32; 4]>::new(&instance, ShaderStage::VERTEX, 0);
+    }
+}
@@ -0,0 +1,235 @@
+use std::collections::HashSet;
+
+use super::{
+    full_mip_level_count, texture_format_features, BindGroupDescriptor, BindGroupLayoutDescriptor,
+    BindingType, BufferDescriptor, BufferUsage, ComputePipelineDescriptor, Features, Instance,
+    PipelineLayoutDescriptor, ProgrammableStageDescriptor, RenderPipelineDescriptor, SampleCount,
+    SamplerDescriptor, ShaderModuleSource, ShaderStage, TextureDescriptor, TextureDimension,
+    ValidationError,
+};
+
+// Checks performed before calling into wgpu, which would panic instead of
+// returning an error. They only cover part of the validation done by wgpu: in
+// particular shader code isn't parsed, so invalid instructions, missing entry
+// points, mismatched stages and interfaces incompatible with the pipeline
+// layout still make wgpu panic.
+
+const SPIRV_MAGIC_NUMBER: u32 = 0x0723_0203;
+
+// Only the SPIR-V magic number is checked.
+pub(crate) fn validate_shader_module(source: &ShaderModuleSource) -> Result<(), ValidationError> {
+    match source {
+        ShaderModuleSource::SpirV(words) if words.first() != Some(&SPIRV_MAGIC_NUMBER) => Err(
+            ValidationError::new("The shader module isn't valid SPIR-V code"),
+        ),
+        _ => Ok(()),
+    }
+}
+
+pub(crate) fn validate_pipeline_layout(
+    instance: &Instance,
+    desc: &PipelineLayoutDescriptor,
+) -> Result<(), ValidationError> {
+    let max_bind_groups = instance.limits().max_bind_groups;
+    if desc.bind_group_layouts.len() > max_bind_groups as usize {
+        return Err(ValidationError::new(format!(
+            "Too many bind group layouts (maximum {})",
+            max_bind_groups
+        )));
+    }
+    if !desc.push_constant_ranges.is_empty() {
+        require_features(instance, Features::PUSH_CONSTANTS)?;
+    }
+    let max_push_constant_size = instance.limits().max_push_constant_size;
+    let mut stages = ShaderStage::NONE;
+    for (index, range) in desc.push_constant_ranges.iter().enumerate() {
+        let align = wgpu::PUSH_CONSTANT_ALIGNMENT;
+        if range.range.start % align != 0 || range.range.end % align != 0 {
+            return Err(ValidationError::new(format!(
+                "Push constant range {} isn't aligned to {} bytes",
+                index, align
+            )));
+        }
+        if range.range.end > max_push_constant_size {
+            return Err(ValidationError::new(format!(
+                "Push constant range {} exceeds the maximum push constant size ({})",
+                index, max_push_constant_size
+            )));
+        }
+        if stages.intersects(range.stages) {
+            return Err(ValidationError::new(format!(
+                "Push constant range {} provides a stage already provided by another range",
+                index
+            )));
+        }
+        stages |= range.stages;
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_render_pipeline(
+    desc: &RenderPipelineDescriptor,
+) -> Result<(), ValidationError> {
+    validate_entry_point("vertex", &desc.vertex_stage)?;
+    if let Some(fragment_stage) = &desc.fragment_stage {
+        validate_entry_point("fragment", fragment_stage)?;
+    }
+    validate_sample_count(desc.sample_count)
+}
+
+pub(crate) fn validate_compute_pipeline(
+    desc: &ComputePipelineDescriptor,
+) -> Result<(), ValidationError> {
+    validate_entry_point("compute", &desc.compute_stage)
+}
+
+pub(crate) fn validate_buffer(
+    instance: &Instance,
+    desc: &BufferDescriptor,
+) -> Result<(), ValidationError> {
+    if desc.mapped_at_creation && desc.size % wgpu::COPY_BUFFER_ALIGNMENT != 0 {
+        return Err(ValidationError::new(format!(
+            "Buffers mapped at creation must have a size aligned to {} bytes",
+            wgpu::COPY_BUFFER_ALIGNMENT
+        )));
+    }
+    validate_buffer_usage(instance, desc.usage)
+}
+
+pub(crate) fn validate_buffer_usage(
+    instance: &Instance,
+    usage: BufferUsage,
+) -> Result<(), ValidationError> {
+    if instance
+        .features()
+        .contains(Features::MAPPABLE_PRIMARY_BUFFERS)
+    {
+        return Ok(());
+    }
+    if (usage.contains(BufferUsage::MAP_READ)
+        && !(BufferUsage::MAP_READ | BufferUsage::COPY_DST).contains(usage))
+        || (usage.contains(BufferUsage::MAP_WRITE)
+            && !(BufferUsage::MAP_WRITE | BufferUsage::COPY_SRC).contains(usage))
+    {
+        return Err(ValidationError::new(format!(
+            "Map usages can only be combined with the opposite copy usage ({:?})",
+            usage
+        )));
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_bind_group_layout(
+    instance: &Instance,
+    desc: &BindGroupLayoutDescriptor,
+) -> Result<(), ValidationError> {
+    let mut bindings = HashSet::new();
+    for entry in desc.entries.iter() {
+        if !bindings.insert(entry.binding) {
+            return Err(ValidationError::new(format!(
+                "Conflicting binding {}",
+                entry.binding
+            )));
+        }
+        if entry.count.is_some() {
+            match entry.ty {
+                BindingType::SampledTexture { .. } => {
+                    require_features(instance, Features::SAMPLED_TEXTURE_BINDING_ARRAY)?
+                }
+                _ => {
+                    return Err(ValidationError::new(format!(
+                        "Binding {} can't be an array",
+                        entry.binding
+                    )))
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_bind_group(desc: &BindGroupDescriptor) -> Result<(), ValidationError> {
+    let mut bindings = HashSet::new();
+    for entry in desc.entries.iter() {
+        if !bindings.insert(entry.binding) {
+            return Err(ValidationError::new(format!(
+                "Conflicting binding {}",
+                entry.binding
+            )));
+        }
+    }
+    Ok(())
+}
+
+pub(crate) fn validate_texture(
+    instance: &Instance,
+    desc: &TextureDescriptor,
+) -> Result<(), ValidationError> {
+    let size = desc.size;
+    if size.width == 0 || size.height == 0 || size.depth == 0 {
+        return Err(ValidationError::new("Textures can't have a zero size"));
+    }
+    if desc.dimension == TextureDimension::D1 && size.height != 1 {
+        return Err(ValidationError::new("1D textures must have a height of 1"));
+    }
+    if desc.mip_level_count == 0 || desc.mip_level_count > full_mip_level_count(&size) {
+        return Err(ValidationError::new(format!(
+            "Invalid mip level count ({})",
+            desc.mip_level_count
+        )));
+    }
+    validate_sample_count(desc.sample_count)?;
+    require_features(instance, texture_format_features(desc.format))
+}
+
+pub(crate) fn validate_sampler(desc: &SamplerDescriptor) -> Result<(), ValidationError> {
+    if desc.lod_min_clamp > desc.lod_max_clamp {
+        return Err(ValidationError::new(
+            "The minimum level of detail clamp exceeds the maximum one",
+        ));
+    }
+    if let Some(clamp) = desc.anisotropy_clamp {
+        if !matches!(clamp.get(), 1 | 2 | 4 | 8 | 16) {
+            return Err(ValidationError::new(format!(
+                "Invalid anisotropy clamp ({}), must be one of 1, 2, 4, 8 or 16",
+                clamp
+            )));
+        }
+    }
+    Ok(())
+}
+
+// The entry point itself is only looked up by wgpu.
+fn validate_entry_point(
+    stage: &str,
+    desc: &ProgrammableStageDescriptor,
+) -> Result<(), ValidationError> {
+    if desc.entry_point.is_empty() {
+        return Err(ValidationError::new(format!(
+            "Missing {} stage entry point name",
+            stage
+        )));
+    }
+    Ok(())
+}
+
+fn validate_sample_count(sample_count: SampleCount) -> Result<(), ValidationError> {
+    if sample_count == 0 || sample_count > 32 || !sample_count.is_power_of_two() {
+        return Err(ValidationError::new(format!(
+            "Invalid sample count ({})",
+            sample_count
+        )));
+    }
+    Ok(())
+}
+
+fn require_features(instance: &Instance, features: Features) -> Result<(), ValidationError> {
+    if instance.features().contains(features) {
+        Ok(())
+    } else {
+        Err(ValidationError::new(format!(
+            "Missing features ({:?})",
+            features - instance.features()
+        )))
+    }
+}
@@ -75,6 +75,10 @@ pub struct RenderPipelineDescriptor {
     pub write_mask: core::ColorWrite,
     pub color_buffer_format: core::CanvasColorBufferFormat,
     pub sample_count: core::SampleCount,
+    // Number of uniform slots allocated at once when push constants aren't
+    // available. More are allocated if there are more draws between two
+    // submissions.
+    pub uniform_buffer_capacity: u32,
 }
 
 impl Default for RenderPipelineDescriptor {
@@ -93,6 +97,7 @@ impl Default for RenderPipelineDescriptor {
             write_mask: core::ColorWrite::ALL,
             color_buffer_format: core::CanvasColorBufferFormat::default(),
             sample_count: 1,
+            uniform_buffer_capacity: 1024,
         }
     }
 }
@@ -100,27 +105,53 @@ impl Default for RenderPipelineDescriptor {
 #[derive(Debug)]
 pub struct RenderPipeline {
     pipeline: core::RenderPipeline,
-    sample_count: core::SampleCount,
-    color_buffer_format: core::CanvasColorBufferFormat,
+    push_constants_buffer: Option<core::DynamicUniformBuffer<PushConstants>>,
+    descriptor: RenderPipelineDescriptor,
 }
 
 impl RenderPipeline {
     pub fn new(instance: &core::Instance, desc: &RenderPipelineDescriptor) -> Self {
-        let pipeline_layout = core::PipelineLayout::new(
-            &instance,
-            &core::PipelineLayoutDescriptor {
-                label: None,
-                bind_group_layouts: &[],
-                push_constant_ranges: &[core::PushConstantRange {
-                    stages: core::ShaderStage::VERTEX,
-                    range: 0..std::mem::size_of::<PushConstants>() as u32,
-                }],
-            },
-        );
-        let vs_module = core::ShaderModule::new(
-            &instance,
-            core::include_spirv!("shaders/gen/spirv/shape2.vert.spv"),
-        );
+        let push_constants_buffer = if instance.features().contains(core::Features::PUSH_CONSTANTS)
+        {
+            None
+        } else {
+            Some(core::DynamicUniformBuffer::new(
+                instance,
+                core::ShaderStage::VERTEX,
+                desc.uniform_buffer_capacity,
+            ))
+        };
+        let pipeline_layout = match &push_constants_buffer {
+            Some(push_constants_buffer) => core::PipelineLayout::new(
+                &instance,
+                &core::PipelineLayoutDescriptor {
+                    label: Some("shape2 pipeline layout"),
+                    bind_group_layouts: &[push_constants_buffer.bind_group_layout()],
+                    push_constant_ranges: &[],
+                },
+            ),
+            None => core::PipelineLayout::new(
+                &instance,
+                &core::PipelineLayoutDescriptor {
+                    label: Some("shape2 pipeline layout"),
+                    bind_group_layouts: &[],
+                    push_constant_ranges: &[core::PushConstantRange {
+                        stages: core::ShaderStage::VERTEX,
+                        range: 0..std::mem::size_of::<PushConstants>() as u32,
+                    }],
+                },
+            ),
+        };
+        let vs_module = match &push_constants_buffer {
+            Some(_) => core::ShaderModule::new(
+                &instance,
+                core::include_spirv!("shaders/gen/spirv/shape2_uniform.vert.spv"),
+            ),
+            None => core::ShaderModule::new(
+                &instance,
+                core::include_spirv!("shaders/gen/spirv/shape2.vert.spv"),
+            ),
+        };
         let fs_module = core::ShaderModule::new(
             &instance,
             core::include_spirv!("shaders/gen/spirv/shape2.frag.spv"),
@@ -128,7 +159,7 @@ impl RenderPipeline {
         let pipeline = core::RenderPipeline::new(
             &instance,
             &core::RenderPipelineDescriptor {
-                label: None,
+                label: Some("shape2 render pipeline"),
                 layout: Some(&pipeline_layout),
                 vertex_stage: core::ProgrammableStageDescriptor {
                     module: &vs_module,
@@ -170,15 +201,24 @@ impl RenderPipeline {
         );
         Self {
             pipeline,
-            sample_count: desc.sample_count,
-            color_buffer_format: desc.color_buffer_format,
+            push_constants_buffer,
+            descriptor: desc.clone(),
         }
     }
 
+    // Recreates the GPU objects, e.g. after the instance has been recreated.
+    pub fn rebuild(&mut self, instance: &core::Instance) {
+        *self = Self::new(instance, &self.descriptor);
+    }
+
+    pub fn uses_push_constants(&self) -> bool {
+        self.push_constants_buffer.is_none()
+    }
+
     pub fn render_pass_requirements(&self) -> core::RenderPassRequirements {
         core::RenderPassRequirements {
-            sample_count: self.sample_count,
-            color_buffer_formats: vec![self.color_buffer_format],
+            sample_count: self.descriptor.sample_count,
+            color_buffer_formats: vec![self.descriptor.color_buffer_format],
             depth_stencil_buffer_format: None,
         }
     }
@@ -203,7 +243,27 @@ pub trait Renderer<'a> {
         RangeIt: IntoIterator<Item = core::MeshIndexRange>;
 }
 
-impl<'a> Renderer<'a> for core::RenderPass<'a> {
+fn set_push_constants<'a, E: core::RenderEncoder<'a>>(
+    pass: &mut E,
+    pipeline: &'a RenderPipeline,
+    push_constants: &PushConstants,
+) {
+    match &pipeline.push_constants_buffer {
+        Some(push_constants_buffer) => {
+            // Replaying a bundle would read ring slots overwritten since it was
+            // recorded.
+            let (bind_group, offset) = if pass.is_render_bundle_encoder() {
+                push_constants_buffer.push_persistent(push_constants)
+            } else {
+                push_constants_buffer.push(push_constants)
+            };
+            pass.set_bind_group(0, bind_group, &[offset]);
+        }
+        None => pass.set_push_constants(core::ShaderStage::VERTEX, 0, push_constants.as_slice()),
+    }
+}
+
+impl<'a, E: core::RenderEncoder<'a>> Renderer<'a> for E {
     fn draw_shape2(
         &mut self,
         pipeline: &'a RenderPipeline,
@@ -214,7 +274,7 @@ impl<'a> Renderer<'a> for core::RenderPass<'a> {
         self.set_pipeline(&pipeline.pipeline);
         self.set_index_buffer(mesh.index_buffer().slice(..));
         self.set_vertex_buffer(0, mesh.vertex_buffer().slice(..));
-        self.set_push_constants(core::ShaderStage::VERTEX, 0, push_constants.as_slice());
+        set_push_constants(self, pipeline, push_constants);
         self.draw_indexed(index_range, 0, 0..1);
     }
 
@@ -232,7 +292,7 @@ impl<'a> Renderer<'a> for core::RenderPass<'a> {
             self.set_index_buffer(mesh.index_buffer().slice(..));
             self.set_vertex_buffer(0, mesh.vertex_buffer().slice(..));
             for (pc, ranges) in pcs.into_iter() {
-                self.set_push_constants(core::ShaderStage::VERTEX, 0, pc.as_slice());
+                set_push_constants(self, pipeline, pc);
                 for range in ranges.into_iter() {
                     self.draw_indexed(range, 0, 0..1);
                 }
@@ -245,9 +305,214 @@ impl<'a> Renderer<'a> for core::RenderPass<'a> {
 mod tests {
     use super::*;
 
+    use galvanic_assert::{matchers::*, *};
+
+    use crate::core::Canvas;
+
     #[test]
     fn creation() {
         let instance = core::Instance::new(&core::InstanceDescriptor::default()).unwrap();
         let _pipeline = RenderPipeline::new(&instance, &RenderPipelineDescriptor::default());
     }
+
+    #[test]
+    fn creation_without_push_constants() {
+        let instance = core::Instance::new(&core::InstanceDescriptor {
+            optional_features: core::Features::empty(),
+            ..core::InstanceDescriptor::default()
+        })
+        .unwrap();
+        let pipeline = RenderPipeline::new(&instance, &RenderPipelineDescriptor::default());
+        expect_that!(!pipeline.uses_push_constants());
+    }
+
+    fn create_target(instance: &core::Instance) -> core::CanvasTexture {
+        core::CanvasTexture::new(
+            instance,
+            &core::CanvasTextureDescriptor {
+                size: core::CanvasSize::new(4, 4),
+                color_buffer_descriptors: vec![core::CanvasTextureColorBufferDescriptor {
+                    format: core::CanvasColorBufferFormat::Rgba8Unorm,
+                    usage: core::CanvasColorBufferUsage::COPY_SRC,
+                }],
+                ..core::CanvasTextureDescriptor::default()
+            },
+        )
+        .unwrap()
+    }
+
+    fn create_full_screen_mesh(instance: &core::Instance) -> Mesh {
+        Mesh::new(
+            instance,
+            &[
+                Vertex::new([-1., -1.]),
+                Vertex::new([3., -1.]),
+                Vertex::new([-1., 3.]),
+            ],
+            &[0, 1, 2],
+        )
+    }
+
+    fn draw_full_screen(
+        instance: &core::Instance,
+        pipeline: &RenderPipeline,
+        color: core::ColorF32,
+    ) -> image::RgbaImage {
+        let mesh = create_full_screen_mesh(instance);
+        let push_constants = PushConstants::new(&geometry2::Transform::identity(), color);
+        let mut texture = create_target(instance);
+        let frame = texture.current_frame(instance).unwrap();
+        let mut cmd_seq = core::CommandSequence::new(instance);
+        {
+            let mut rpass = cmd_seq
+                .begin_render_pass(
+                    &frame,
+                    &pipeline.render_pass_requirements(),
+                    &core::RenderPassOperations::default(),
+                )
+                .unwrap();
+            rpass.draw_shape2(pipeline, &mesh, &push_constants, 0..mesh.index_count());
+        }
+        cmd_seq.submit(instance);
+        texture.color_texture(0).unwrap().to_image(instance)
+    }
+
+    fn instance_without_push_constants() -> core::Instance {
+        core::Instance::new(&core::InstanceDescriptor {
+            optional_features: core::Features::empty(),
+            ..core::InstanceDescriptor::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn draw_without_push_constants() {
+        let instance = instance_without_push_constants();
+        let pipeline = RenderPipeline::new(
+            &instance,
+            &RenderPipelineDescriptor {
+                color_buffer_format: core::CanvasColorBufferFormat::Rgba8Unorm,
+                uniform_buffer_capacity: 1,
+                ..RenderPipelineDescriptor::default()
+            },
+        );
+        // A single uniform slot is enough when submitting after each draw.
+        for (color, expected) in &[
+            (core::ColorF32::RED, [255, 0, 0, 255]),
+            (core::ColorF32::BLUE, [0, 0, 255, 255]),
+        ] {
+            let image = draw_full_screen(&instance, &pipeline, *color);
+            for pixel in image.pixels() {
+                expect_that!(&pixel.0, eq(*expected));
+            }
+        }
+    }
+
+    #[test]
+    fn rebuild() {
+        let mut instance = core::Instance::new(&core::InstanceDescriptor::default()).unwrap();
+        let desc = RenderPipelineDescriptor {
+            sample_count: 2,
+            ..RenderPipelineDescriptor::default()
+        };
+        let mut pipeline = RenderPipeline::new(&instance, &desc);
+        instance.recreate(None).unwrap();
+        pipeline.rebuild(&instance);
+        expect_that!(&pipeline.render_pass_requirements().sample_count, eq(2));
+    }
+
+    #[test]
+    fn rebuild_without_push_constants() {
+        let mut instance = instance_without_push_constants();
+        let mut pipeline = RenderPipeline::new(
+            &instance,
+            &RenderPipelineDescriptor {
+                color_buffer_format: core::CanvasColorBufferFormat::Rgba8Unorm,
+                ..RenderPipelineDescriptor::default()
+            },
+        );
+        instance.recreate(None).unwrap();
+        pipeline.rebuild(&instance);
+        let image = draw_full_screen(&instance, &pipeline, core::ColorF32::RED);
+        for pixel in image.pixels() {
+            expect_that!(&pixel.0, eq([255, 0, 0, 255]));
+        }
+    }
+
+    fn draw_bundle(instance: &core::Instance) -> image::RgbaImage {
+        // A single ring slot, reused by the draw recorded in the render pass.
+        let pipeline = RenderPipeline::new(
+            instance,
+            &RenderPipelineDescriptor {
+                color_buffer_format: core::CanvasColorBufferFormat::Rgba8Unorm,
+                uniform_buffer_capacity: 1,
+                ..RenderPipelineDescriptor::default()
+            },
+        );
+        let mesh = create_full_screen_mesh(instance);
+        let bundle_push_constants =
+            PushConstants::new(&geometry2::Transform::identity(), core::ColorF32::RED);
+        let pass_push_constants =
+            PushConstants::new(&geometry2::Transform::identity(), core::ColorF32::BLUE);
+        let requirements = pipeline.render_pass_requirements();
+
+        let mut encoder =
+            core::RenderBundleEncoder::from_render_pass_requirements(instance, None, &requirements);
+        encoder.draw_shape2(
+            &pipeline,
+            &mesh,
+            &bundle_push_constants,
+            0..mesh.index_count(),
+        );
+        let bundle = encoder.finish(&core::RenderBundleDescriptor::default());
+
+        let mut texture = create_target(instance);
+        // Replay the same bundle twice, as done for static content.
+        for _ in 0..2 {
+            let frame = texture.current_frame(instance).unwrap();
+            let mut cmd_seq = core::CommandSequence::new(instance);
+            {
+                let mut rpass = cmd_seq
+                    .begin_render_pass(
+                        &frame,
+                        &requirements,
+                        &core::RenderPassOperations {
+                            color_operations: vec![core::ColorOperations {
+                                load: core::LoadOp::Clear(core::ColorF64::BLACK),
+                                store: true,
+                            }],
+                            ..core::RenderPassOperations::default()
+                        },
+                    )
+                    .unwrap();
+                rpass.draw_shape2(
+                    &pipeline,
+                    &mesh,
+                    &pass_push_constants,
+                    0..mesh.index_count(),
+                );
+                rpass.execute_bundles(std::iter::once(&*bundle));
+            }
+            cmd_seq.submit(instance);
+        }
+        texture.color_texture(0).unwrap().to_image(instance)
+    }
+
+    #[test]
+    fn render_bundle() {
+        let instance = core::Instance::new(&core::InstanceDescriptor::default()).unwrap();
+        let image = draw_bundle(&instance);
+        for pixel in image.pixels() {
+            expect_that!(&pixel.0, eq([255, 0, 0, 255]));
+        }
+    }
+
+    #[test]
+    fn render_bundle_without_push_constants() {
+        let instance = instance_without_push_constants();
+        let image = draw_bundle(&instance);
+        for pixel in image.pixels() {
+            expect_that!(&pixel.0, eq([255, 0, 0, 255]));
+        }
+    }
 }
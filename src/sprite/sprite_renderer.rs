@@ -107,20 +107,39 @@ impl MeshTemplates for Mesh {
     }
 }
 
+#[repr(C)]
 #[derive(Debug, PartialEq, Clone, Copy, serde::Serialize, serde::Deserialize)]
 pub struct PushConstants {
     transform: geometry3::HomogeneousMatrix<f32>,
     color: core::ColorF32,
+    texture_layer: u32,
+    #[serde(skip)]
+    _padding: [u32; 3],
 }
 
 impl PushConstants {
     pub fn new(transform: &geometry2::Transform<f32>, color: core::ColorF32) -> Self {
+        Self::with_texture_layer(transform, color, 0)
+    }
+
+    // The texture layer is only used by pipelines sampling from texture arrays.
+    pub fn with_texture_layer(
+        transform: &geometry2::Transform<f32>,
+        color: core::ColorF32,
+        texture_layer: u32,
+    ) -> Self {
         Self {
             transform: transform.to_homogeneous3(),
             color,
+            texture_layer,
+            _padding: [0; 3],
         }
     }
 
+    pub fn texture_layer(&self) -> u32 {
+        self.texture_layer
+    }
+
     fn as_slice(&self) -> &[u32] {
         let pc: *const PushConstants = self;
         let pc: *const u8 = pc as *const u8;
@@ -134,17 +153,19 @@ unsafe impl bytemuck::Zeroable for PushConstants {
         Self {
             transform: geometry3::HomogeneousMatrix::zero(),
             color: core::ColorF32::default(),
+            texture_layer: 0,
+            _padding: [0; 3],
         }
     }
 }
 
 unsafe impl bytemuck::Pod for PushConstants {}
 
-fn bind_group_layout(instance: &core::Instance) -> core::BindGroupLayout {
+fn bind_group_layout(instance: &core::Instance, texture_array: bool) -> core::BindGroupLayout {
     core::BindGroupLayout::new(
         instance,
         &core::BindGroupLayoutDescriptor {
-            label: None,
+            label: Some("sprite uniform constants bind group layout"),
             entries: &[
                 core::BindGroupLayoutEntry {
                     binding: 0,
@@ -152,7 +173,11 @@ fn bind_group_layout(instance: &core::Instance) -> core::BindGroupLayout {
                     ty: core::BindingType::SampledTexture {
                         multisampled: false,
                         component_type: core::TextureComponentType::Float,
-                        dimension: core::TextureViewDimension::D2,
+                        dimension: if texture_array {
+                            core::TextureViewDimension::D2Array
+                        } else {
+                            core::TextureViewDimension::D2
+                        },
                     },
                     count: None,
                 },
@@ -170,6 +195,8 @@ fn bind_group_layout(instance: &core::Instance) -> core::BindGroupLayout {
 #[derive(Debug)]
 pub struct UniformConstants {
     bind_group: core::BindGroup,
+    // Generation of the canvas texture the constants sample from, if any.
+    source_generation: Option<u64>,
 }
 
 impl UniformConstants {
@@ -178,11 +205,52 @@ impl UniformConstants {
         texture: &core::TextureView,
         sampler: &core::Sampler,
     ) -> Self {
-        let layout = bind_group_layout(instance);
+        Self::create(instance, texture, sampler, false)
+    }
+
+    // To be used with pipelines sampling from texture arrays.
+    pub fn new_array(
+        instance: &core::Instance,
+        texture_array: &core::TextureView,
+        sampler: &core::Sampler,
+    ) -> Self {
+        Self::create(instance, texture_array, sampler, true)
+    }
+
+    // Samples from a color buffer of the canvas texture, returns None if the
+    // color buffer doesn't exist. The constants must be recreated when they
+    // become outdated, as resizing the canvas recreates its textures.
+    pub fn from_canvas_texture(
+        instance: &core::Instance,
+        canvas: &core::CanvasTexture,
+        color_buffer_index: usize,
+        sampler: &core::Sampler,
+    ) -> Option<Self> {
+        let texture = canvas.color_texture_view(color_buffer_index)?;
+        let mut uniform_constants = Self::create(instance, texture, sampler, false);
+        uniform_constants.source_generation = Some(canvas.generation());
+        Some(uniform_constants)
+    }
+
+    // Always false for constants not created from a canvas texture.
+    pub fn is_outdated(&self, canvas: &core::CanvasTexture) -> bool {
+        match self.source_generation {
+            Some(generation) => generation != canvas.generation(),
+            None => false,
+        }
+    }
+
+    fn create(
+        instance: &core::Instance,
+        texture: &core::TextureView,
+        sampler: &core::Sampler,
+        texture_array: bool,
+    ) -> Self {
+        let layout = bind_group_layout(instance, texture_array);
         let bind_group = core::BindGroup::new(
             instance,
             &core::BindGroupDescriptor {
-                label: None,
+                label: Some("sprite uniform constants bind group"),
                 layout: &layout,
                 entries: &[
                     core::BindGroupEntry {
@@ -196,7 +264,10 @@ impl UniformConstants {
                 ],
             },
         );
-        Self { bind_group }
+        Self {
+            bind_group,
+            source_generation: None,
+        }
     }
 }
 
@@ -207,6 +278,11 @@ pub struct RenderPipelineDescriptor {
     pub write_mask: core::ColorWrite,
     pub color_buffer_format: core::CanvasColorBufferFormat,
     pub sample_count: core::SampleCount,
+    // Number of uniform slots allocated at once when push constants aren't
+    // available. More are allocated if there are more draws between two
+    // submissions.
+    pub uniform_buffer_capacity: u32,
+    pub texture_array: bool,
 }
 
 impl Default for RenderPipelineDescriptor {
@@ -225,6 +301,8 @@ impl Default for RenderPipelineDescriptor {
             write_mask: core::ColorWrite::ALL,
             color_buffer_format: core::CanvasColorBufferFormat::default(),
             sample_count: 1,
+            uniform_buffer_capacity: 1024,
+            texture_array: false,
         }
     }
 }
@@ -233,36 +311,72 @@ impl Default for RenderPipelineDescriptor {
 pub struct RenderPipeline {
     pipeline: core::RenderPipeline,
     bind_group_layout: core::BindGroupLayout,
-    sample_count: core::SampleCount,
-    color_buffer_format: core::CanvasColorBufferFormat,
+    push_constants_buffer: Option<core::DynamicUniformBuffer<PushConstants>>,
+    descriptor: RenderPipelineDescriptor,
 }
 
 impl RenderPipeline {
     pub fn new(instance: &core::Instance, desc: &RenderPipelineDescriptor) -> Self {
-        let bind_group_layout = bind_group_layout(instance);
-        let pipeline_layout = core::PipelineLayout::new(
-            instance,
-            &core::PipelineLayoutDescriptor {
-                label: None,
-                bind_group_layouts: &[&bind_group_layout],
-                push_constant_ranges: &[core::PushConstantRange {
-                    stages: core::ShaderStage::VERTEX,
-                    range: 0..std::mem::size_of::<PushConstants>() as u32,
-                }],
-            },
-        );
+        let bind_group_layout = bind_group_layout(instance, desc.texture_array);
+        let push_constants_buffer = if instance.features().contains(core::Features::PUSH_CONSTANTS)
+        {
+            None
+        } else {
+            Some(core::DynamicUniformBuffer::new(
+                instance,
+                core::ShaderStage::VERTEX,
+                desc.uniform_buffer_capacity,
+            ))
+        };
+        let pipeline_layout = match &push_constants_buffer {
+            Some(push_constants_buffer) => core::PipelineLayout::new(
+                instance,
+                &core::PipelineLayoutDescriptor {
+                    label: Some("sprite pipeline layout"),
+                    bind_group_layouts: &[
+                        &bind_group_layout,
+                        push_constants_buffer.bind_group_layout(),
+                    ],
+                    push_constant_ranges: &[],
+                },
+            ),
+            None => core::PipelineLayout::new(
+                instance,
+                &core::PipelineLayoutDescriptor {
+                    label: Some("sprite pipeline layout"),
+                    bind_group_layouts: &[&bind_group_layout],
+                    push_constant_ranges: &[core::PushConstantRange {
+                        stages: core::ShaderStage::VERTEX,
+                        range: 0..std::mem::size_of::<PushConstants>() as u32,
+                    }],
+                },
+            ),
+        };
         let vs_module = core::ShaderModule::new(
             instance,
-            core::include_spirv!("shaders/gen/spirv/sprite.vert.spv"),
+            match (&push_constants_buffer, desc.texture_array) {
+                (Some(_), false) => {
+                    core::include_spirv!("shaders/gen/spirv/sprite_uniform.vert.spv")
+                }
+                (Some(_), true) => {
+                    core::include_spirv!("shaders/gen/spirv/sprite_array_uniform.vert.spv")
+                }
+                (None, false) => core::include_spirv!("shaders/gen/spirv/sprite.vert.spv"),
+                (None, true) => core::include_spirv!("shaders/gen/spirv/sprite_array.vert.spv"),
+            },
         );
         let fs_module = core::ShaderModule::new(
             instance,
-            core::include_spirv!("shaders/gen/spirv/sprite.frag.spv"),
+            if desc.texture_array {
+                core::include_spirv!("shaders/gen/spirv/sprite_array.frag.spv")
+            } else {
+                core::include_spirv!("shaders/gen/spirv/sprite.frag.spv")
+            },
         );
         let pipeline = core::RenderPipeline::new(
             instance,
             &core::RenderPipelineDescriptor {
-                label: None,
+                label: Some("sprite render pipeline"),
                 layout: Some(&pipeline_layout),
                 vertex_stage: core::ProgrammableStageDescriptor {
                     module: &vs_module,
@@ -312,15 +426,24 @@ impl RenderPipeline {
         Self {
             pipeline,
             bind_group_layout,
-            sample_count: desc.sample_count,
-            color_buffer_format: desc.color_buffer_format,
+            push_constants_buffer,
+            descriptor: desc.clone(),
         }
     }
 
+    // Recreates the GPU objects, e.g. after the instance has been recreated.
+    pub fn rebuild(&mut self, instance: &core::Instance) {
+        *self = Self::new(instance, &self.descriptor);
+    }
+
+    pub fn uses_push_constants(&self) -> bool {
+        self.push_constants_buffer.is_none()
+    }
+
     pub fn render_pass_requirements(&self) -> core::RenderPassRequirements {
         core::RenderPassRequirements {
-            sample_count: self.sample_count,
-            color_buffer_formats: vec![self.color_buffer_format],
+            sample_count: self.descriptor.sample_count,
+            color_buffer_formats: vec![self.descriptor.color_buffer_format],
             depth_stencil_buffer_format: None,
         }
     }
@@ -347,7 +470,27 @@ pub trait Renderer<'a> {
         RangeIt: IntoIterator<Item = core::MeshIndexRange>;
 }
 
-impl<'a> Renderer<'a> for core::RenderPass<'a> {
+fn set_push_constants<'a, E: core::RenderEncoder<'a>>(
+    pass: &mut E,
+    pipeline: &'a RenderPipeline,
+    push_constants: &PushConstants,
+) {
+    match &pipeline.push_constants_buffer {
+        Some(push_constants_buffer) => {
+            // Replaying a bundle would read ring slots overwritten since it was
+            // recorded.
+            let (bind_group, offset) = if pass.is_render_bundle_encoder() {
+                push_constants_buffer.push_persistent(push_constants)
+            } else {
+                push_constants_buffer.push(push_constants)
+            };
+            pass.set_bind_group(1, bind_group, &[offset]);
+        }
+        None => pass.set_push_constants(core::ShaderStage::VERTEX, 0, push_constants.as_slice()),
+    }
+}
+
+impl<'a, E: core::RenderEncoder<'a>> Renderer<'a> for E {
     fn draw_sprite(
         &mut self,
         pipeline: &'a RenderPipeline,
@@ -360,7 +503,7 @@ impl<'a> Renderer<'a> for core::RenderPass<'a> {
         self.set_bind_group(0, &uniform_constants.bind_group, &[]);
         self.set_index_buffer(mesh.index_buffer().slice(..));
         self.set_vertex_buffer(0, mesh.vertex_buffer().slice(..));
-        self.set_push_constants(core::ShaderStage::VERTEX, 0, push_constants.as_slice());
+        set_push_constants(self, pipeline, push_constants);
         self.draw_indexed(index_range, 0, 0..1);
     }
 
@@ -381,7 +524,7 @@ impl<'a> Renderer<'a> for core::RenderPass<'a> {
                 self.set_index_buffer(mesh.index_buffer().slice(..));
                 self.set_vertex_buffer(0, mesh.vertex_buffer().slice(..));
                 for (pc, ranges) in pcs.into_iter() {
-                    self.set_push_constants(core::ShaderStage::VERTEX, 0, pc.as_slice());
+                    set_push_constants(self, pipeline, pc);
                     for range in ranges.into_iter() {
                         self.draw_indexed(range, 0, 0..1);
                     }
@@ -395,9 +538,196 @@ impl<'a> Renderer<'a> for core::RenderPass<'a> {
 mod tests {
     use super::*;
 
+    use galvanic_assert::{matchers::*, *};
+
+    use crate::core::Canvas;
+
     #[test]
     fn creation() {
         let instance = core::Instance::new(&core::InstanceDescriptor::default()).unwrap();
         let _pipeline = RenderPipeline::new(&instance, &RenderPipelineDescriptor::default());
     }
+
+    #[test]
+    fn creation_without_push_constants() {
+        let instance = core::Instance::new(&core::InstanceDescriptor {
+            optional_features: core::Features::empty(),
+            ..core::InstanceDescriptor::default()
+        })
+        .unwrap();
+        let pipeline = RenderPipeline::new(&instance, &RenderPipelineDescriptor::default());
+        expect_that!(!pipeline.uses_push_constants());
+    }
+
+    #[test]
+    fn creation_with_texture_array() {
+        let instance = core::Instance::new(&core::InstanceDescriptor::default()).unwrap();
+        let _pipeline = RenderPipeline::new(
+            &instance,
+            &RenderPipelineDescriptor {
+                texture_array: true,
+                ..RenderPipelineDescriptor::default()
+            },
+        );
+    }
+
+    #[test]
+    fn creation_with_texture_array_without_push_constants() {
+        let instance = core::Instance::new(&core::InstanceDescriptor {
+            optional_features: core::Features::empty(),
+            ..core::InstanceDescriptor::default()
+        })
+        .unwrap();
+        let pipeline = RenderPipeline::new(
+            &instance,
+            &RenderPipelineDescriptor {
+                texture_array: true,
+                ..RenderPipelineDescriptor::default()
+            },
+        );
+        expect_that!(!pipeline.uses_push_constants());
+    }
+
+    #[test]
+    fn uniform_constants_from_canvas_texture() {
+        let instance = core::Instance::new(&core::InstanceDescriptor::default()).unwrap();
+        let mut canvas = core::CanvasTexture::new(
+            &instance,
+            &core::CanvasTextureDescriptor {
+                size: core::CanvasSize::new(4, 4),
+                color_buffer_descriptors: vec![core::CanvasTextureColorBufferDescriptor {
+                    format: core::CanvasColorBufferFormat::Rgba8Unorm,
+                    usage: core::CanvasColorBufferUsage::SAMPLED,
+                }],
+                ..core::CanvasTextureDescriptor::default()
+            },
+        )
+        .unwrap();
+        let sampler = core::Sampler::new(&instance, &core::SamplerDescriptor::default());
+        expect_that!(
+            UniformConstants::from_canvas_texture(&instance, &canvas, 1, &sampler).is_none()
+        );
+
+        let uniform_constants =
+            UniformConstants::from_canvas_texture(&instance, &canvas, 0, &sampler).unwrap();
+        expect_that!(!uniform_constants.is_outdated(&canvas));
+        canvas.resize(&instance, core::CanvasSize::new(8, 8));
+        expect_that!(uniform_constants.is_outdated(&canvas));
+    }
+
+    fn draw_bundle(instance: &core::Instance) -> image::RgbaImage {
+        // A single ring slot, reused by the draw recorded in the render pass.
+        let pipeline = RenderPipeline::new(
+            instance,
+            &RenderPipelineDescriptor {
+                color_buffer_format: core::CanvasColorBufferFormat::Rgba8Unorm,
+                uniform_buffer_capacity: 1,
+                ..RenderPipelineDescriptor::default()
+            },
+        );
+        let texture = core::Texture::from_image(
+            instance,
+            None,
+            &image::DynamicImage::ImageRgba8(image::RgbaImage::from_pixel(
+                2,
+                2,
+                image::Rgba([255, 0, 0, 255]),
+            )),
+            core::ColorSpace::Linear,
+            core::TextureUsage::SAMPLED,
+        );
+        let view = texture.create_view(&core::TextureViewDescriptor::default());
+        let sampler = core::Sampler::new(instance, &core::SamplerDescriptor::default());
+        let uniform_constants = UniformConstants::new(instance, &view, &sampler);
+        let mesh = Mesh::new(
+            instance,
+            &[
+                Vertex::new([-1., -1.], [0., 0.]),
+                Vertex::new([3., -1.], [2., 0.]),
+                Vertex::new([-1., 3.], [0., 2.]),
+            ],
+            &[0, 1, 2],
+        );
+        let bundle_push_constants =
+            PushConstants::new(&geometry2::Transform::identity(), core::ColorF32::WHITE);
+        let pass_push_constants =
+            PushConstants::new(&geometry2::Transform::identity(), core::ColorF32::BLACK);
+        let requirements = pipeline.render_pass_requirements();
+
+        let mut encoder =
+            core::RenderBundleEncoder::from_render_pass_requirements(instance, None, &requirements);
+        encoder.draw_sprite(
+            &pipeline,
+            &uniform_constants,
+            &mesh,
+            &bundle_push_constants,
+            0..mesh.index_count(),
+        );
+        let bundle = encoder.finish(&core::RenderBundleDescriptor::default());
+
+        let mut canvas = core::CanvasTexture::new(
+            instance,
+            &core::CanvasTextureDescriptor {
+                size: core::CanvasSize::new(4, 4),
+                color_buffer_descriptors: vec![core::CanvasTextureColorBufferDescriptor {
+                    format: core::CanvasColorBufferFormat::Rgba8Unorm,
+                    usage: core::CanvasColorBufferUsage::COPY_SRC,
+                }],
+                ..core::CanvasTextureDescriptor::default()
+            },
+        )
+        .unwrap();
+        for _ in 0..2 {
+            let frame = canvas.current_frame(instance).unwrap();
+            let mut cmd_seq = core::CommandSequence::new(instance);
+            {
+                let mut rpass = cmd_seq
+                    .begin_render_pass(
+                        &frame,
+                        &requirements,
+                        &core::RenderPassOperations {
+                            color_operations: vec![core::ColorOperations {
+                                load: core::LoadOp::Clear(core::ColorF64::BLACK),
+                                store: true,
+                            }],
+                            ..core::RenderPassOperations::default()
+                        },
+                    )
+                    .unwrap();
+                rpass.draw_sprite(
+                    &pipeline,
+                    &uniform_constants,
+                    &mesh,
+                    &pass_push_constants,
+                    0..mesh.index_count(),
+                );
+                rpass.execute_bundles(std::iter::once(&*bundle));
+            }
+            cmd_seq.submit(instance);
+        }
+
+        canvas.color_texture(0).unwrap().to_image(instance)
+    }
+
+    #[test]
+    fn render_bundle() {
+        let instance = core::Instance::new(&core::InstanceDescriptor::default()).unwrap();
+        let image = draw_bundle(&instance);
+        for pixel in image.pixels() {
+            expect_that!(&pixel.0, eq([255, 0, 0, 255]));
+        }
+    }
+
+    #[test]
+    fn render_bundle_without_push_constants() {
+        let instance = core::Instance::new(&core::InstanceDescriptor {
+            optional_features: core::Features::empty(),
+            ..core::InstanceDescriptor::default()
+        })
+        .unwrap();
+        let image = draw_bundle(&instance);
+        for pixel in image.pixels() {
+            expect_that!(&pixel.0, eq([255, 0, 0, 255]));
+        }
+    }
 }